@@ -0,0 +1,880 @@
+pub use appstore_types;
+pub use appstore_types::*;
+pub use hc_crud;
+pub use hdk_extensions::hdk;
+pub use apphub_sdk;
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use hdk::prelude::*;
+use hc_crud::{
+    Entity,
+    GetEntityInput,
+    UpdateEntityInput,
+};
+use hdk_extensions::{
+    UpdateEntryInput,
+};
+use appstore_types::coop_content_sdk::{
+    GroupEntry,
+};
+use apphub_sdk::{
+    AppEntryInput as AppHubAppEntryInput,
+    WebAppEntryInput,
+    WebAppPackageEntryInput,
+    WebAppPackageVersionEntryInput,
+    apphub_types::{
+        UiEntry,
+        mere_memory_types::{
+            MemoryEntry,
+            MemoryBlockEntry,
+        },
+    },
+    dnahub_sdk::{
+        DnaEntryInput,
+    },
+    zomehub_types::{
+        ZomeEntry,
+    },
+};
+
+
+/// Name of the coordinator zome that these types and calls are for
+pub const APPSTORE_ZOME : &str = "appstore_csr";
+
+
+/// Create `with_<field>` setters for the optional fields of an input struct
+macro_rules! optional_setters {
+    ( $struct:ident, $( $field:ident: $type:ty ),* $(,)? ) => {
+        impl $struct {
+            $(
+                pub fn $field(mut self, value: $type) -> Self {
+                    self.$field = Some( value );
+                    self
+                }
+            )*
+        }
+    };
+}
+
+
+//
+// Common Inputs
+//
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetForAgentInput {
+    pub for_agent: AgentPubKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetForPublisherInput {
+    pub for_publisher: EntityId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetForAppInput {
+    pub for_app: EntityId,
+}
+
+
+//
+// Publisher
+//
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePublisherInput {
+    pub name: String,
+    pub location: String,
+    pub website: WebAddress,
+
+    // optional
+    pub description: Option<String>,
+    pub email: Option<String>,
+    pub icon: Option<EntryHash>,
+    pub editors: Option<Vec<AgentPubKey>>,
+
+    pub published_at: Option<u64>,
+    pub last_updated: Option<u64>,
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+    pub provenance: Option<Provenance>,
+}
+
+impl CreatePublisherInput {
+    pub fn new(name: &str, location: &str, website: WebAddress) -> Self {
+        CreatePublisherInput {
+            name: name.to_string(),
+            location: location.to_string(),
+            website,
+            description: None,
+            email: None,
+            icon: None,
+            editors: None,
+            published_at: None,
+            last_updated: None,
+            metadata: None,
+            provenance: None,
+        }
+    }
+}
+optional_setters!( CreatePublisherInput,
+    description: String,
+    email: String,
+    icon: EntryHash,
+    editors: Vec<AgentPubKey>,
+    published_at: u64,
+    last_updated: u64,
+    metadata: BTreeMap<String, RmpvValue>,
+    provenance: Provenance,
+);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatePublisherProperties {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub website: Option<WebAddress>,
+    pub icon: Option<EntryHash>,
+    pub email: Option<String>,
+    pub editors: Option<Vec<AgentPubKey>>,
+    pub published_at: Option<u64>,
+    pub last_updated: Option<u64>,
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+}
+optional_setters!( UpdatePublisherProperties,
+    name: String,
+    description: String,
+    location: String,
+    website: WebAddress,
+    icon: EntryHash,
+    email: String,
+    editors: Vec<AgentPubKey>,
+    published_at: u64,
+    last_updated: u64,
+    metadata: BTreeMap<String, RmpvValue>,
+);
+pub type UpdatePublisherInput = UpdateEntityInput<UpdatePublisherProperties>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecatePublisherInput {
+    pub base: ActionHash,
+    pub message: String,
+
+    // optional
+    pub recommended_alternatives: Option<Vec<ActionHash>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndeprecatePublisherInput {
+    pub base: ActionHash,
+}
+
+
+//
+// App
+//
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAppInput {
+    pub title: String,
+    pub subtitle: String,
+    pub description: String,
+    pub icon: EntryHash,
+    pub publisher: EntityId,
+    pub apphub_hrl: HRL,
+    pub apphub_hrl_hash: EntryHash,
+
+    // optional
+    pub editors: Option<Vec<AgentPubKey>>,
+
+    pub published_at: Option<u64>,
+    pub last_updated: Option<u64>,
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+    pub source_ownership: Option<SourceOwnershipProof>,
+    pub prove_source_ownership: Option<bool>,
+    pub provenance: Option<Provenance>,
+}
+
+impl CreateAppInput {
+    pub fn new(
+        title: &str,
+        subtitle: &str,
+        description: &str,
+        icon: EntryHash,
+        publisher: EntityId,
+        apphub_hrl: HRL,
+        apphub_hrl_hash: EntryHash,
+    ) -> Self {
+        CreateAppInput {
+            title: title.to_string(),
+            subtitle: subtitle.to_string(),
+            description: description.to_string(),
+            icon,
+            publisher,
+            apphub_hrl,
+            apphub_hrl_hash,
+            editors: None,
+            published_at: None,
+            last_updated: None,
+            metadata: None,
+            source_ownership: None,
+            prove_source_ownership: None,
+            provenance: None,
+        }
+    }
+}
+optional_setters!( CreateAppInput,
+    editors: Vec<AgentPubKey>,
+    published_at: u64,
+    last_updated: u64,
+    metadata: BTreeMap<String, RmpvValue>,
+    source_ownership: SourceOwnershipProof,
+    prove_source_ownership: bool,
+    provenance: Provenance,
+);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateAppProperties {
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<EntryHash>,
+    pub apphub_hrl: Option<HRL>,
+    pub apphub_hrl_hash: Option<EntryHash>,
+    pub editors: Option<Vec<AgentPubKey>>,
+    pub published_at: Option<u64>,
+    pub last_updated: Option<u64>,
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+    pub source_ownership: Option<SourceOwnershipProof>,
+}
+optional_setters!( UpdateAppProperties,
+    title: String,
+    subtitle: String,
+    description: String,
+    icon: EntryHash,
+    apphub_hrl: HRL,
+    apphub_hrl_hash: EntryHash,
+    editors: Vec<AgentPubKey>,
+    published_at: u64,
+    last_updated: u64,
+    metadata: BTreeMap<String, RmpvValue>,
+    source_ownership: SourceOwnershipProof,
+);
+pub type UpdateAppInput = UpdateEntityInput<UpdateAppProperties>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecateAppInput {
+    pub base: ActionHash,
+    pub message: String,
+
+    // optional
+    pub recommended_alternatives: Option<Vec<ActionHash>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndeprecateAppInput {
+    pub base: ActionHash,
+}
+
+
+//
+// App Version
+//
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAppVersionInput {
+    pub version: String,
+    pub for_app: EntityId,
+    pub apphub_hrl: HRL,
+    pub apphub_hrl_hash: EntryHash,
+    pub bundle_hashes: BundleHashes,
+
+    // optional
+    pub published_at: Option<u64>,
+    pub last_updated: Option<u64>,
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+    pub verify: Option<bool>,
+    pub dna_hashes: Option<Vec<DnaHash>>,
+    pub delegation: Option<ActionHash>,
+    pub provenance: Option<Provenance>,
+    /// Manifest summary from an earlier verification (ignored when `verify` is set)
+    pub manifest: Option<ManifestSummary>,
+}
+
+impl CreateAppVersionInput {
+    pub fn new(
+        version: &str,
+        for_app: EntityId,
+        apphub_hrl: HRL,
+        apphub_hrl_hash: EntryHash,
+        bundle_hashes: BundleHashes,
+    ) -> Self {
+        CreateAppVersionInput {
+            version: version.to_string(),
+            for_app,
+            apphub_hrl,
+            apphub_hrl_hash,
+            bundle_hashes,
+            published_at: None,
+            last_updated: None,
+            metadata: None,
+            verify: None,
+            dna_hashes: None,
+            delegation: None,
+            provenance: None,
+            manifest: None,
+        }
+    }
+}
+optional_setters!( CreateAppVersionInput,
+    published_at: u64,
+    last_updated: u64,
+    metadata: BTreeMap<String, RmpvValue>,
+    verify: bool,
+    dna_hashes: Vec<DnaHash>,
+    delegation: ActionHash,
+    provenance: Provenance,
+    manifest: ManifestSummary,
+);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateAppVersionProperties {
+    pub version: Option<String>,
+    pub published_at: Option<u64>,
+    pub last_updated: Option<u64>,
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+}
+optional_setters!( UpdateAppVersionProperties,
+    version: String,
+    published_at: u64,
+    last_updated: u64,
+    metadata: BTreeMap<String, RmpvValue>,
+);
+pub type UpdateAppVersionInput = UpdateEntityInput<UpdateAppVersionProperties>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteAppVersionInput {
+    pub base: ActionHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuePublishingDelegationInput {
+    pub for_app: EntityId,
+    pub delegate: AgentPubKey,
+
+    // optional
+    pub expires_at: Option<u64>,
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+}
+
+impl IssuePublishingDelegationInput {
+    pub fn new(for_app: EntityId, delegate: AgentPubKey) -> Self {
+        IssuePublishingDelegationInput {
+            for_app,
+            delegate,
+            expires_at: None,
+            metadata: None,
+        }
+    }
+}
+optional_setters!( IssuePublishingDelegationInput,
+    expires_at: u64,
+    metadata: BTreeMap<String, RmpvValue>,
+);
+
+/// An agent's App Store entities exported from one DNA for import into its successor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationExport {
+    pub dna: DnaHash,
+    pub author: AgentPubKey,
+    pub publishers: Vec<Entity<PublisherEntry>>,
+    pub apps: Vec<Entity<AppEntry>>,
+    pub app_versions: Vec<Entity<AppVersionEntry>>,
+    pub memories: Vec<MigrationMemory>,
+}
+
+/// Entities created by `import_my_entries` or `import_catalog_snapshot`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationImportReport {
+    pub publishers: Vec<Entity<PublisherEntry>>,
+    pub apps: Vec<Entity<AppEntry>>,
+    pub app_versions: Vec<Entity<AppVersionEntry>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenChainInput {
+    pub previous_dna: DnaHash,
+    pub close_action: ActionHash,
+}
+
+/// Start of a [`ChangeLogPage`]; `after` skips changes at exactly `since` up to that action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetChangesSinceInput {
+    pub since: u64,
+    pub after: Option<ActionHash>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeLogPage {
+    pub changes: Vec<ChangeLogItem>,
+    /// There are more changes after the last one in this page
+    pub more: bool,
+}
+
+/// The governance group's moderation log is always included; `groups` adds others
+///
+/// `page` starts at 0 and `page_size` defaults to [`CATALOG_SNAPSHOT_PAGE_SIZE`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportCatalogSnapshotInput {
+    pub groups: Option<Vec<ActionHash>>,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+/// Entities re-written by `upgrade_my_entries`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaUpgradeReport {
+    pub publishers: Vec<Entity<PublisherEntry>>,
+    pub apps: Vec<Entity<AppEntry>>,
+    pub app_versions: Vec<Entity<AppVersionEntry>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnaAppVersion {
+    pub app: Entity<AppEntry>,
+    pub app_version: Entity<AppVersionEntry>,
+}
+
+
+//
+// DevHub Packages
+//
+/// The DevHub DNAs that hold the DNA and zome entries of an App Version's bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetDownloadManifestInput {
+    pub id: ActionHash,
+    pub dnahub_dna: DnaHash,
+    pub zomehub_dna: DnaHash,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DownloadResourceType {
+    Ui,
+    Happ,
+    Dna,
+    Zome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DownloadBlock {
+    /// [`EntryHash`] of the `MemoryBlockEntry`
+    pub address: EntryHash,
+    /// Length of the block's bytes
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadMemory {
+    pub address: EntryHash,
+    pub hash: String,
+    pub compression: Option<String>,
+    pub uncompressed_size: Option<u64>,
+    pub memory_size: u64,
+    pub blocks: Vec<DownloadBlock>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadResource {
+    /// Bundled path of the resource; nested paths are joined with `/` (eg. `<happ>/<dna>/<zome>`)
+    pub path: String,
+    pub resource_type: DownloadResourceType,
+    /// [`EntryHash`] of the DevHub `UiEntry`, `AppEntry`, `DnaEntry` or `ZomeEntry`
+    pub address: EntryHash,
+    /// The memory holding the resource's bytes (UI and zome resources)
+    pub memory: Option<DownloadMemory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadManifest {
+    pub app_version: ActionHash,
+    pub host_dna: DnaHash,
+    pub dnahub_dna: DnaHash,
+    pub zomehub_dna: DnaHash,
+    pub webapp_package_version: AnyDhtHash,
+    pub webapp: EntryHash,
+    pub bundle_hashes: BundleHashes,
+    pub resources: Vec<DownloadResource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetDeltaInput {
+    pub from: ActionHash,
+    pub to: ActionHash,
+    pub dnahub_dna: DnaHash,
+    pub zomehub_dna: DnaHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryDelta {
+    pub path: String,
+    pub resource_type: DownloadResourceType,
+    pub address: EntryHash,
+    pub hash: String,
+    /// Blocks of the target memory that are already in the base version
+    pub unchanged_blocks: Vec<EntryHash>,
+    /// Blocks of the target memory that must be fetched
+    pub changed_blocks: Vec<DownloadBlock>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppVersionDelta {
+    pub from: ActionHash,
+    pub to: ActionHash,
+    pub host_dna: DnaHash,
+    pub dnahub_dna: DnaHash,
+    pub zomehub_dna: DnaHash,
+    /// Resources (without their memory) whose DevHub entry is not in the base version
+    pub changed_resources: Vec<DownloadResource>,
+    pub memories: Vec<MemoryDelta>,
+    /// Total size of the distinct changed blocks
+    pub download_size: u64,
+}
+
+
+//
+// Mirrors
+//
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorAppVersionInput {
+    pub id: ActionHash,
+    /// An already saved mere_memory address of the bundle (otherwise it is fetched from a host)
+    pub address: Option<EntryHash>,
+}
+
+
+//
+// Host Liveness
+//
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordHostResponseInput {
+    pub dna: DnaHash,
+    pub zome: String,
+    pub function: String,
+    pub host: AgentPubKey,
+    pub responded: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRankedHostsInput {
+    pub dna: DnaHash,
+    pub zome: String,
+    pub function: String,
+    /// Candidate hosts (eg. from the Portal); when omitted only recorded hosts are ranked
+    pub hosts: Option<Vec<AgentPubKey>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedHost {
+    pub host: AgentPubKey,
+    pub last_pong: Option<u64>,
+    pub last_failure: Option<u64>,
+    pub failures: u32,
+}
+
+
+//
+// Moderation
+//
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetModeratorActionsInput {
+    pub group_id: ActionHash,
+    pub app_id: ActionHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateModeratorActionInput {
+    pub group_id: ActionHash,
+    pub app_id: ActionHash,
+    pub message: String,
+    pub metadata: BTreeMap<String, RmpvValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPublisherModeratorActionsInput {
+    pub group_id: ActionHash,
+    pub publisher_id: ActionHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdatePublisherModeratorActionInput {
+    pub group_id: ActionHash,
+    pub publisher_id: ActionHash,
+    pub message: String,
+    pub metadata: BTreeMap<String, RmpvValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAppealInput {
+    pub moderator_action: ActionHash,
+    pub message: String,
+
+    // optional
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveAppealInput {
+    pub appeal: ActionHash,
+    pub message: String,
+
+    // optional
+    pub restore: Option<bool>,
+    pub metadata: Option<BTreeMap<String, RmpvValue>>,
+}
+
+
+//
+// Viewpoints
+//
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewpointGetAppsInput {
+    pub group_id: ActionHash,
+
+    // optional
+    pub after: Option<ViewpointAppsCursor>,
+    pub limit: Option<usize>,
+}
+
+/// Position of an app in the viewpoint listing (its link time, then its ID)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewpointAppsCursor {
+    pub timestamp: Timestamp,
+    pub app_id: ActionHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewpointAppsPage {
+    pub items: Vec<Entity<AppEntry>>,
+    pub total: usize,
+
+    // optional
+    pub next: Option<ViewpointAppsCursor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewpointGetInput {
+    pub group_id: ActionHash,
+    pub id: ActionHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewpointEntity<T> {
+    pub entity: Entity<T>,
+    pub moderated_state: Option<Entity<ModeratorActionEntry>>,
+    pub removed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetMyViewpointInput {
+    pub group_ids: Vec<ActionHash>,
+}
+
+
+
+//
+// Calls
+//
+/// Call an `appstore_csr` function in the given cell and decode its response
+pub fn call_appstore<I, O>(
+    target: CallTargetCell,
+    function: &str,
+    input: I,
+) -> ExternResult<O>
+where
+    I: Serialize + Debug,
+    O: serde::de::DeserializeOwned + Debug,
+{
+    let response = call(
+        target,
+        APPSTORE_ZOME,
+        function.into(),
+        None,
+        input,
+    )?;
+
+    match response {
+        ZomeCallResponse::Ok(extern_io) => extern_io.decode()
+            .map_err(|err| wasm_error!(WasmErrorInner::Guest(format!(
+                "Failed to decode '{}' response: {:?}", function, err,
+            )))),
+        other => Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Call to '{}' failed: {:?}", function, other,
+        )))),
+    }
+}
+
+
+/// A cell running the App Store DNA (eg. a local coordinator or a bridged role)
+///
+/// ```no_run
+/// use appstore_sdk::{
+///     AppStoreCell,
+///     hdk::prelude::*,
+/// };
+///
+/// fn is_group_admin(group_id: ActionHash) -> ExternResult<bool> {
+///     let appstore = AppStoreCell::role("appstore");
+///     let agent = appstore.whoami(())?.agent_initial_pubkey;
+///     let group = appstore.get_group( group_id )?;
+///
+///     Ok( group.content.admins.contains( &agent ) )
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AppStoreCell {
+    pub target: CallTargetCell,
+}
+
+impl AppStoreCell {
+    /// The App Store cell that this zome is running in
+    pub fn local() -> Self {
+        AppStoreCell {
+            target: CallTargetCell::Local,
+        }
+    }
+
+    /// An App Store cell in the same hApp, by role name
+    pub fn role(role_name: &str) -> Self {
+        AppStoreCell {
+            target: CallTargetCell::OtherRole( role_name.into() ),
+        }
+    }
+
+    /// An App Store cell by cell ID
+    pub fn cell(cell_id: CellId) -> Self {
+        AppStoreCell {
+            target: CallTargetCell::OtherCell( cell_id ),
+        }
+    }
+}
+
+
+/// Create typed [`AppStoreCell`] methods for `appstore_csr` functions
+macro_rules! appstore_calls {
+    ( $( $name:ident( $input:ty ) -> $output:ty; )* ) => {
+        impl AppStoreCell {
+            $(
+                pub fn $name(&self, input: $input) -> ExternResult<$output> {
+                    call_appstore( self.target.clone(), stringify!($name), input )
+                }
+            )*
+        }
+    };
+}
+
+appstore_calls! {
+    whoami( () ) -> AgentInfo;
+    get_read_only_functions( () ) -> Vec<String>;
+
+    // Groups
+    create_group( GroupEntry ) -> Entity<GroupEntry>;
+    get_group( ActionHash ) -> Entity<GroupEntry>;
+    update_group( UpdateEntryInput<GroupEntry> ) -> Entity<GroupEntry>;
+
+    // DevHub entry hashes
+    hash_webapp_package_entry( WebAppPackageEntryInput ) -> EntryHash;
+    hash_webapp_package_version_entry( WebAppPackageVersionEntryInput ) -> EntryHash;
+    hash_webapp_entry( WebAppEntryInput ) -> EntryHash;
+    hash_ui_entry( UiEntry ) -> EntryHash;
+    hash_app_entry( AppHubAppEntryInput ) -> EntryHash;
+    hash_mere_memory_entry( MemoryEntry ) -> EntryHash;
+    hash_mere_memory_block_entry( MemoryBlockEntry ) -> EntryHash;
+    hash_dna_entry( DnaEntryInput ) -> EntryHash;
+    hash_zome_entry( ZomeEntry ) -> EntryHash;
+
+    // Publishers
+    create_publisher( CreatePublisherInput ) -> Entity<PublisherEntry>;
+    get_publisher( GetEntityInput ) -> Entity<PublisherEntry>;
+    update_publisher( UpdatePublisherInput ) -> Entity<PublisherEntry>;
+    deprecate_publisher( DeprecatePublisherInput ) -> Entity<PublisherEntry>;
+    undeprecate_publisher( UndeprecatePublisherInput ) -> Entity<PublisherEntry>;
+    get_publishers_for_agent( GetForAgentInput ) -> Vec<Entity<PublisherEntry>>;
+    get_my_publishers( () ) -> Vec<Entity<PublisherEntry>>;
+    get_all_publishers( Option<bool> ) -> Vec<Entity<PublisherEntry>>;
+
+    // Apps
+    create_app( CreateAppInput ) -> Entity<AppEntry>;
+    get_app( GetEntityInput ) -> Entity<AppEntry>;
+    get_app_for_apphub_hrl( EntryHash ) -> Entity<AppEntry>;
+    backfill_apphub_hrl_claims( () ) -> usize;
+    update_app( UpdateAppInput ) -> Entity<AppEntry>;
+    deprecate_app( DeprecateAppInput ) -> Entity<AppEntry>;
+    undeprecate_app( UndeprecateAppInput ) -> Entity<AppEntry>;
+    get_apps_for_agent( GetForAgentInput ) -> Vec<Entity<AppEntry>>;
+    get_apps_for_publisher( GetForPublisherInput ) -> Vec<Entity<AppEntry>>;
+    get_my_apps( () ) -> Vec<Entity<AppEntry>>;
+    get_all_apps( Option<bool> ) -> Vec<Entity<AppEntry>>;
+
+    // App Versions
+    create_app_version( CreateAppVersionInput ) -> Entity<AppVersionEntry>;
+    get_app_version( GetEntityInput ) -> Entity<AppVersionEntry>;
+    update_app_version( UpdateAppVersionInput ) -> Entity<AppVersionEntry>;
+    delete_app_version( DeleteAppVersionInput ) -> ActionHash;
+    get_app_versions_for_app( GetForAppInput ) -> Vec<Entity<AppVersionEntry>>;
+    get_app_versions_for_dna( DnaHash ) -> Vec<DnaAppVersion>;
+    issue_publishing_delegation( IssuePublishingDelegationInput ) -> Entity<PublishingDelegationEntry>;
+    get_publishing_delegations( GetForAppInput ) -> Vec<Entity<PublishingDelegationEntry>>;
+    revoke_publishing_delegation( GetEntityInput ) -> ActionHash;
+    upgrade_my_publishers( () ) -> Vec<Entity<PublisherEntry>>;
+    upgrade_my_apps( () ) -> Vec<Entity<AppEntry>>;
+    upgrade_my_app_versions( () ) -> Vec<Entity<AppVersionEntry>>;
+    upgrade_my_entries( () ) -> SchemaUpgradeReport;
+    export_my_entries( () ) -> MigrationExport;
+    close_my_chain( DnaHash ) -> ActionHash;
+    open_my_chain( OpenChainInput ) -> ActionHash;
+    import_my_entries( MigrationExport ) -> MigrationImportReport;
+    export_catalog_snapshot( ExportCatalogSnapshotInput ) -> SerializedBytes;
+    get_changes_since( GetChangesSinceInput ) -> ChangeLogPage;
+    import_catalog_snapshot( SerializedBytes ) -> MigrationImportReport;
+
+    // DevHub Packages
+    verify_app_version( GetEntityInput ) -> Entity<BundleVerificationEntry>;
+    get_bundle_verifications( GetEntityInput ) -> Vec<Entity<BundleVerificationEntry>>;
+    inspect_webhapp_bundle( SerializedBytes ) -> ManifestSummary;
+    sign_source_ownership_claim( SourceOwnershipClaim ) -> SourceOwnershipProof;
+    get_app_version_download_manifest( GetDownloadManifestInput ) -> DownloadManifest;
+    get_app_version_delta( GetDeltaInput ) -> AppVersionDelta;
+    mirror_app_version( MirrorAppVersionInput ) -> EntryHash;
+    get_app_version_mirrors( GetEntityInput ) -> Vec<EntryHash>;
+    get_app_version_bundle( GetEntityInput ) -> SerializedBytes;
+    record_host_response( RecordHostResponseInput ) -> Entity<HostLivenessEntry>;
+    get_ranked_hosts( GetRankedHostsInput ) -> Vec<RankedHost>;
+
+    // Moderation
+    get_moderator_actions( GetModeratorActionsInput ) -> Vec<Entity<ModeratorActionEntry>>;
+    get_moderated_state( GetModeratorActionsInput ) -> Option<Entity<ModeratorActionEntry>>;
+    update_moderated_state( UpdateModeratorActionInput ) -> Entity<ModeratorActionEntry>;
+    get_publisher_moderator_actions( GetPublisherModeratorActionsInput ) -> Vec<Entity<ModeratorActionEntry>>;
+    get_publisher_moderated_state( GetPublisherModeratorActionsInput ) -> Option<Entity<ModeratorActionEntry>>;
+    update_publisher_moderated_state( UpdatePublisherModeratorActionInput ) -> Entity<ModeratorActionEntry>;
+    get_moderation_log( ActionHash ) -> Vec<ModerationLogItem>;
+    export_moderation_log( ActionHash ) -> SerializedBytes;
+    verify_moderation_log_export( SerializedBytes ) -> ModerationLogVerification;
+    create_appeal( CreateAppealInput ) -> Entity<AppealEntry>;
+    get_appeals_for_moderator_action( ActionHash ) -> Vec<Entity<AppealEntry>>;
+    get_appeal_resolution( ActionHash ) -> Option<Entity<AppealResolutionEntry>>;
+    get_open_appeals( ActionHash ) -> Vec<Entity<AppealEntry>>;
+    resolve_appeal( ResolveAppealInput ) -> Entity<AppealResolutionEntry>;
+    get_governance_agents( () ) -> Vec<AgentPubKey>;
+    get_governance_group( () ) -> Option<ActionHash>;
+    register_governance_group( ActionHash ) -> ActionHash;
+    get_governance_removed_apps( () ) -> Vec<Entity<AppEntry>>;
+
+    // Viewpoints
+    viewpoint_get_all_apps( ActionHash ) -> Vec<Entity<AppEntry>>;
+    viewpoint_get_all_removed_apps( ActionHash ) -> Vec<Entity<AppEntry>>;
+    viewpoint_get_apps( ViewpointGetAppsInput ) -> ViewpointAppsPage;
+    viewpoint_get_app( ViewpointGetInput ) -> ViewpointEntity<AppEntry>;
+    viewpoint_get_publisher( ViewpointGetInput ) -> ViewpointEntity<PublisherEntry>;
+    backfill_removed_subject_index( ActionHash ) -> usize;
+    get_my_viewpoint( () ) -> Option<Entity<ViewpointPreferenceEntry>>;
+    set_my_viewpoint( SetMyViewpointInput ) -> Entity<ViewpointPreferenceEntry>;
+    my_view_get_all_apps( () ) -> Vec<Entity<AppEntry>>;
+}
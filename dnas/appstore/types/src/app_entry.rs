@@ -1,4 +1,6 @@
 use crate::{
+    rmpv,
+    Provenance,
     HRL,
     EntityId,
     RmpvValue,
@@ -9,6 +11,55 @@ use std::collections::BTreeMap;
 use hdi::prelude::*;
 
 
+/// Signature by a DevHub package maintainer over a [`SourceOwnershipClaim`]
+///
+/// Validation cannot read the DevHub DNA, so the proof carries the msgpack value of the
+/// `WebAppPackageEntry`; it must hash to `apphub_hrl_hash` and name the signer as maintainer.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SourceOwnershipProof {
+    pub signer: AgentPubKey,
+    pub signature: Signature,
+    #[serde(default)]
+    pub package: RmpvValue,
+}
+
+impl SourceOwnershipProof {
+    /// The [`EntryHash`] of the included DevHub package entry
+    pub fn package_hash(&self) -> ExternResult<EntryHash> {
+        let mut bytes = vec![];
+
+        rmpv::encode::write_value( &mut bytes, &self.package )
+            .map_err(|err| wasm_error!(WasmErrorInner::Guest(format!(
+                "Failed to encode DevHub package entry: {}", err,
+            ))))?;
+
+        hash_entry( Entry::App( AppEntryBytes(
+            SerializedBytes::from( UnsafeBytes::from( bytes ) )
+        )))
+    }
+
+    /// The agent named by the included package's `maintainer` field (if it is an agent)
+    pub fn package_maintainer(&self) -> Option<AgentPubKey> {
+        let (_, maintainer) = self.package.as_map()?.iter()
+            .find( |(key, _)| key.as_str() == Some("maintainer") )?;
+        let (variant, agent) = maintainer.as_map()?.first()?;
+
+        match variant.as_str()? {
+            "Agent" => AgentPubKey::try_from_raw_39( agent.as_slice()?.to_vec() ).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// The data signed to prove that a publisher may list a DevHub package
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SourceOwnershipClaim {
+    pub apphub_hrl: HRL,
+    pub apphub_hrl_hash: EntryHash,
+    pub publisher: EntityId,
+}
+
+
 //
 // App Entry
 //
@@ -28,10 +79,37 @@ pub struct AppEntry {
     pub author: AgentPubKey,
     pub published_at: u64,
     pub last_updated: u64,
+    #[serde(default)]
     pub metadata: BTreeMap<String, RmpvValue>,
 
     // optional
+    #[serde(default)]
     pub deprecation: Option<DeprecationNotice>,
+    #[serde(default)]
+    pub source_ownership: Option<SourceOwnershipProof>,
+    /// Set when the entity was imported from a previous DNA
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+
+    /// See [`SchemaVersioned`](crate::SchemaVersioned)
+    #[serde(default)]
+    pub schema_version: u32,
+}
+
+impl AppEntry {
+    /// The claim that a [`SourceOwnershipProof`] for this entry must sign
+    pub fn source_ownership_claim(&self) -> SourceOwnershipClaim {
+        SourceOwnershipClaim {
+            apphub_hrl: self.apphub_hrl.clone(),
+            apphub_hrl_hash: self.apphub_hrl_hash.clone(),
+            publisher: self.publisher.clone(),
+        }
+    }
+
+    /// Whether the DevHub package maintainer has vouched for this listing
+    pub fn source_ownership_proven(&self) -> bool {
+        self.source_ownership.is_some()
+    }
 }
 
 impl<'a> CommonFields<'a> for AppEntry {
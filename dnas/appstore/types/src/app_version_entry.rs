@@ -1,4 +1,5 @@
 use crate::{
+    Provenance,
     HRL,
     EntityId,
     RmpvValue,
@@ -16,6 +17,40 @@ pub struct BundleHashes {
 }
 
 
+/// Summary of an integrity zome found in a DNA bundle
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ZomeSummary {
+    pub name: String,
+    /// Hex encoded SHA-256 of the zome's WASM bytes (not a Holochain `WasmHash`)
+    #[serde(alias = "wasm_hash")]
+    pub wasm_sha256: String,
+}
+
+/// Summary of a role found in a hApp bundle
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RoleSummary {
+    pub name: String,
+    pub dna_name: String,
+    /// The `installed_hash` declared by the role manifest (if any); it is not computed from the
+    /// DNA bundle
+    #[serde(alias = "dna_hash")]
+    pub installed_hash: Option<String>,
+    pub network_seed: Option<String>,
+    pub integrity_zomes: Vec<ZomeSummary>,
+}
+
+/// Summary of the manifests inside a webhapp bundle
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ManifestSummary {
+    pub name: String,
+    pub happ_name: String,
+    pub happ_description: Option<String>,
+    pub ui_hash: String,
+    pub happ_hash: String,
+    pub roles: Vec<RoleSummary>,
+}
+
+
 //
 // App Version Entry
 //
@@ -32,7 +67,47 @@ pub struct AppVersionEntry {
     pub author: AgentPubKey,
     pub published_at: u64,
     pub last_updated: u64,
+    #[serde(default)]
     pub metadata: BTreeMap<String, RmpvValue>,
+
+    // optional
+    #[serde(default)]
+    pub manifest: Option<ManifestSummary>,
+    #[serde(default)]
+    pub dna_hashes: Option<Vec<DnaHash>>,
+    /// Publishing delegation that authorizes a non-editor author
+    #[serde(default)]
+    pub delegation: Option<ActionHash>,
+    /// Set when the entity was imported from a previous DNA
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+
+    /// See [`SchemaVersioned`](crate::SchemaVersioned)
+    #[serde(default)]
+    pub schema_version: u32,
+}
+
+impl AppVersionEntry {
+    /// DNA hashes declared at creation plus any `installed_hash` declared in the manifest summary
+    pub fn all_dna_hashes(&self) -> Vec<DnaHash> {
+        let mut hashes = self.dna_hashes.clone()
+            .unwrap_or_default();
+
+        if let Some(manifest) = &self.manifest {
+            for role in manifest.roles.iter() {
+                let dna_hash = role.installed_hash.as_ref()
+                    .and_then( |hash| DnaHash::try_from( hash.as_str() ).ok() );
+
+                if let Some(dna_hash) = dna_hash {
+                    if !hashes.contains( &dna_hash ) {
+                        hashes.push( dna_hash );
+                    }
+                }
+            }
+        }
+
+        hashes
+    }
 }
 
 impl<'a> CommonFields<'a> for AppVersionEntry {
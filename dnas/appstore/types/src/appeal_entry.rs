@@ -0,0 +1,44 @@
+use crate::{
+    RmpvValue,
+};
+
+use coop_content_sdk::{
+    group_ref,
+};
+use std::collections::BTreeMap;
+use hdi::prelude::*;
+
+
+//
+// Appeal Entry
+//
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AppealEntry {
+    pub subject_id: ActionHash,
+    pub moderator_action: ActionHash,
+    pub group_id: ActionHash,
+    pub author: AgentPubKey,
+    pub published_at: u64,
+    pub message: String,
+    pub metadata: BTreeMap<String, RmpvValue>,
+}
+
+
+//
+// Appeal Resolution Entry
+//
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AppealResolutionEntry {
+    pub appeal: ActionHash,
+    pub group_id: (ActionHash, ActionHash),
+    pub author: AgentPubKey,
+    pub published_at: u64,
+    pub message: String,
+    pub metadata: BTreeMap<String, RmpvValue>,
+
+    // optional
+    pub restore_action: Option<ActionHash>,
+}
+group_ref!( AppealResolutionEntry, group_id );
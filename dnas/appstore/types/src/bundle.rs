@@ -0,0 +1,185 @@
+//! Inspect webhapp/happ/dna bundles (gzipped msgpack) and summarize their manifests
+use crate::{
+    RmpvValue,
+    ManifestSummary,
+    RoleSummary,
+    ZomeSummary,
+};
+use std::collections::BTreeMap;
+use std::io::Read;
+use flate2::read::GzDecoder;
+use sha2::{ Sha256, Digest };
+
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("Failed to decompress bundle: {0}")]
+    Decompress(std::io::Error),
+    #[error("Failed to decode bundle msgpack: {0}")]
+    Decode(String),
+    #[error("Bundle is missing field '{0}'")]
+    MissingField(String),
+    #[error("Bundle is missing resource '{0}'")]
+    MissingResource(String),
+    #[error("Bundle resource '{0}' contains a value that is not a byte: {1}")]
+    InvalidResourceByte(String, RmpvValue),
+}
+
+pub type BundleResult<T> = Result<T, BundleError>;
+
+
+/// Hex encoded SHA-256 of the given bytes (same format as mere_memory hashes)
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest( bytes ).iter()
+        .map( |byte| format!("{:02x}", byte ) )
+        .collect()
+}
+
+
+fn field<'a>(value: &'a RmpvValue, key: &str) -> Option<&'a RmpvValue> {
+    value.as_map()?.iter()
+        .find( |(k, _)| k.as_str() == Some(key) )
+        .map( |(_, v)| v )
+}
+
+fn must_field<'a>(value: &'a RmpvValue, key: &str) -> BundleResult<&'a RmpvValue> {
+    field( value, key )
+        .ok_or( BundleError::MissingField( key.to_string() ) )
+}
+
+fn str_field(value: &RmpvValue, key: &str) -> BundleResult<String> {
+    must_field( value, key )?.as_str()
+        .map( |s| s.to_string() )
+        .ok_or( BundleError::MissingField( key.to_string() ) )
+}
+
+fn opt_str_field(value: &RmpvValue, key: &str) -> Option<String> {
+    field( value, key )
+        .and_then( |v| v.as_str() )
+        .map( |s| s.to_string() )
+}
+
+/// Get the resource path of a manifest location (`{ bundled: "<path>" }`)
+fn bundled_path(value: &RmpvValue, key: &str) -> BundleResult<String> {
+    str_field( must_field( value, key )?, "bundled" )
+}
+
+
+/// A decoded bundle: its manifest and the resource bytes keyed by path
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    pub manifest: RmpvValue,
+    pub resources: BTreeMap<String, Vec<u8>>,
+}
+
+impl Bundle {
+    pub fn decode(bytes: &[u8]) -> BundleResult<Self> {
+        let mut msgpack = Vec::new();
+        GzDecoder::new( bytes ).read_to_end( &mut msgpack )
+            .map_err( BundleError::Decompress )?;
+
+        let value = rmpv::decode::read_value( &mut msgpack.as_slice() )
+            .map_err( |err| BundleError::Decode( err.to_string() ) )?;
+
+        let mut resources = BTreeMap::new();
+
+        if let Some(entries) = must_field( &value, "resources" )?.as_map() {
+            for (path, resource) in entries {
+                let path = match path.as_str() {
+                    Some(path) => path.to_string(),
+                    None => continue,
+                };
+                let bytes = match resource {
+                    RmpvValue::Binary(bytes) => bytes.to_owned(),
+                    RmpvValue::Array(items) => items.iter()
+                        .map( |item| item.as_u64()
+                               .and_then( |byte| u8::try_from( byte ).ok() )
+                               .ok_or_else( || BundleError::InvalidResourceByte(
+                                   path.clone(), item.to_owned()
+                               ))
+                        )
+                        .collect::<BundleResult<Vec<u8>>>()?,
+                    _ => continue,
+                };
+
+                resources.insert( path, bytes );
+            }
+        }
+
+        Ok( Bundle {
+            manifest: must_field( &value, "manifest" )?.to_owned(),
+            resources,
+        })
+    }
+
+    pub fn resource(&self, path: &str) -> BundleResult<&Vec<u8>> {
+        self.resources.get( path )
+            .ok_or( BundleError::MissingResource( path.to_string() ) )
+    }
+}
+
+
+/// Summarize the integrity zomes of a DNA bundle
+pub fn summarize_dna(bytes: &[u8]) -> BundleResult<(String, Vec<ZomeSummary>)> {
+    let dna = Bundle::decode( bytes )?;
+    let name = str_field( &dna.manifest, "name" )?;
+    let integrity = must_field( &dna.manifest, "integrity" )?;
+    let mut zomes = vec![];
+
+    if let Some(items) = must_field( integrity, "zomes" )?.as_array() {
+        for zome in items {
+            zomes.push( ZomeSummary {
+                name: str_field( zome, "name" )?,
+                wasm_sha256: sha256_hex( dna.resource( &str_field( zome, "bundled" )? )? ),
+            });
+        }
+    }
+
+    Ok( (name, zomes) )
+}
+
+
+/// Summarize the roles of a hApp bundle
+pub fn summarize_happ(bytes: &[u8]) -> BundleResult<(String, Option<String>, Vec<RoleSummary>)> {
+    let happ = Bundle::decode( bytes )?;
+    let name = str_field( &happ.manifest, "name" )?;
+    let description = opt_str_field( &happ.manifest, "description" );
+    let mut roles = vec![];
+
+    if let Some(items) = must_field( &happ.manifest, "roles" )?.as_array() {
+        for role in items {
+            let dna = must_field( role, "dna" )?;
+            let dna_path = str_field( dna, "bundled" )?;
+            let (dna_name, integrity_zomes) = summarize_dna( happ.resource( &dna_path )? )?;
+
+            roles.push( RoleSummary {
+                name: str_field( role, "name" )?,
+                dna_name,
+                installed_hash: opt_str_field( dna, "installed_hash" ),
+                network_seed: field( dna, "modifiers" )
+                    .and_then( |modifiers| opt_str_field( modifiers, "network_seed" ) ),
+                integrity_zomes,
+            });
+        }
+    }
+
+    Ok( (name, description, roles) )
+}
+
+
+/// Summarize a webhapp bundle
+pub fn summarize_webhapp(bytes: &[u8]) -> BundleResult<ManifestSummary> {
+    let webhapp = Bundle::decode( bytes )?;
+    let ui_bytes = webhapp.resource( &bundled_path( &webhapp.manifest, "ui" )? )?;
+    let happ_bytes = webhapp.resource( &bundled_path( &webhapp.manifest, "happ_manifest" )? )?;
+    let (happ_name, happ_description, roles) = summarize_happ( happ_bytes )?;
+
+    Ok( ManifestSummary {
+        name: str_field( &webhapp.manifest, "name" )?,
+        happ_name,
+        happ_description,
+        ui_hash: sha256_hex( ui_bytes ),
+        happ_hash: sha256_hex( happ_bytes ),
+        roles,
+    })
+}
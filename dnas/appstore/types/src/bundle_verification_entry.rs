@@ -0,0 +1,24 @@
+use crate::{
+    EntityId,
+    BundleHashes,
+};
+use hdi::prelude::*;
+
+
+//
+// Bundle Verification Entry
+//
+/// One agent's attestation that an App Version's bundle hashes do (or do not) match its DevHub
+/// package
+///
+/// Any agent can verify a version so clients should weigh attestations by who made them.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct BundleVerificationEntry {
+    pub app_version: EntityId,
+    /// The App Version's hashes that were checked
+    pub bundle_hashes: BundleHashes,
+    pub verified: bool,
+    pub author: AgentPubKey,
+    pub published_at: u64,
+}
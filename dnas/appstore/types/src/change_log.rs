@@ -0,0 +1,44 @@
+use crate::{
+    EntityId,
+};
+use hdi::prelude::*;
+
+
+//
+// Change Log
+//
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeSubjectType {
+    Publisher,
+    App,
+    AppVersion,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeType {
+    Create,
+    Update,
+    Deprecate,
+    Delete,
+}
+
+/// The link tag of a change-log link; the link target is the action that made the change
+#[derive(Debug, Serialize, Deserialize, Clone, SerializedBytes)]
+pub struct ChangeLogTag {
+    pub subject_type: ChangeSubjectType,
+    pub change_type: ChangeType,
+    pub subject_id: EntityId,
+    /// Time of the change action in milliseconds (this picks the bucket, not the link's time)
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangeLogItem {
+    pub subject_type: ChangeSubjectType,
+    pub change_type: ChangeType,
+    pub subject_id: EntityId,
+    pub action: ActionHash,
+    pub author: AgentPubKey,
+    /// Time of the change in milliseconds
+    pub timestamp: u64,
+}
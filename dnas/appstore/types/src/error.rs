@@ -0,0 +1,468 @@
+use std::fmt;
+use hdi::prelude::*;
+use crate::ModeratorActionType;
+
+
+/// Marker that precedes the hex encoded JSON of an [`AppStoreError`] in a guest error message
+pub const APPSTORE_ERROR_MARKER : &str = "appstore_error:";
+
+
+/// Which action's author failed an editor check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuthorRole {
+    Entry,
+    Update,
+    Delete,
+    Link,
+}
+
+
+/// Structured App Store failures with a stable `code`
+///
+/// The `Display` text is the human readable message; [`AppStoreError::to_message`] appends the
+/// encoded error so that clients can recover the code and fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AppStoreError {
+    AuthorMismatch {
+        entry_author: AgentPubKey,
+        action_author: AgentPubKey,
+    },
+    UnauthorizedEditor {
+        role: AuthorRole,
+        agent: AgentPubKey,
+        editors: Vec<AgentPubKey>,
+    },
+    EditorsChanged {
+        previous: Vec<AgentPubKey>,
+        next: Vec<AgentPubKey>,
+    },
+    ImmutableField {
+        entry_type: String,
+        field: String,
+        previous: String,
+        next: String,
+    },
+    ImmutableEntry {
+        entry_type: String,
+    },
+    DeprecatedEntity,
+    IconTooLarge {
+        entry_type: String,
+        limit: u64,
+    },
+    HrlTargetMismatch {
+        target: EntryHash,
+        apphub_hrl_hash: EntryHash,
+    },
+    HrlDnaNotAllowed {
+        dna: DnaHash,
+        allowed: Vec<DnaHash>,
+    },
+    PackageAlreadyClaimed {
+        apphub_hrl_hash: EntryHash,
+        app: ActionHash,
+    },
+    MissingDelegation {
+        author: AgentPubKey,
+        editors: Vec<AgentPubKey>,
+    },
+    DelegationNotApplicable {
+        delegation: ActionHash,
+        author: AgentPubKey,
+        app: ActionHash,
+        at: u64,
+    },
+    InvalidDelegationExpiry {
+        issued_at: u64,
+        expires_at: u64,
+        max_lifetime: u64,
+    },
+    UnauthorizedDelete {
+        entry_type: String,
+        author: AgentPubKey,
+    },
+    RemovedByGovernance {
+        app: ActionHash,
+    },
+    BundleHashMismatch {
+        expected: String,
+        received: String,
+    },
+    UnverifiedMirror {
+        mirror: EntryHash,
+        expected: String,
+    },
+    UnknownSchemaVersion {
+        version: u32,
+        current: u32,
+    },
+    SchemaDowngrade {
+        previous: u32,
+        next: u32,
+    },
+    OutdatedSchemaVersion {
+        version: u32,
+        current: u32,
+    },
+    InvalidProvenance {
+        dna: DnaHash,
+    },
+    InvalidDnaProperties {
+        reason: String,
+    },
+    NotGovernanceAgent {
+        agent: AgentPubKey,
+        governance_agents: Vec<AgentPubKey>,
+    },
+    NotGovernanceGroup {
+        group: ActionHash,
+    },
+    InvalidModerationSubject {
+        subject: ActionHash,
+    },
+    MissingGroupRevision {
+        group: ActionHash,
+    },
+    SubjectMismatch {
+        context: String,
+        subject: ActionHash,
+        expected: ActionHash,
+    },
+    GroupMismatch {
+        context: String,
+        group: ActionHash,
+        expected: ActionHash,
+    },
+    NotARemoval {
+        moderator_action: ActionHash,
+        action_type: ModeratorActionType,
+    },
+    NotGroupContributor {
+        agent: AgentPubKey,
+        group: ActionHash,
+        revision: ActionHash,
+    },
+    InvalidLinkBase {
+        link_type: String,
+        base: AnyLinkableHash,
+        expected: AnyLinkableHash,
+    },
+    UnclaimedPackage {
+        apphub_hrl_hash: EntryHash,
+    },
+    MissingVerifiedMirror {
+        app_version: ActionHash,
+    },
+    SourcePackageMismatch {
+        package_hash: EntryHash,
+        apphub_hrl_hash: EntryHash,
+    },
+    NotPackageMaintainer {
+        signer: AgentPubKey,
+        maintainer: Option<AgentPubKey>,
+    },
+    InvalidSourceOwnershipSignature {
+        signer: AgentPubKey,
+    },
+    InvalidRemovedSubjectTag {
+        reason: String,
+    },
+    ManifestSummaryMismatch {
+        ui_hash: String,
+        happ_hash: String,
+        bundle_ui_hash: String,
+        bundle_happ_hash: String,
+    },
+    SerializationFailed {
+        context: String,
+        reason: String,
+    },
+    RemoteCallFailed {
+        dna: DnaHash,
+        zome: String,
+        function: String,
+        reason: String,
+    },
+    EntryHashMismatch {
+        expected: EntryHash,
+        received: EntryHash,
+    },
+    InvalidWebhappBundle {
+        reason: String,
+    },
+    MissingWebAppResource {
+        path: String,
+    },
+    AppVersionAppMismatch {
+        from_app: ActionHash,
+        to_app: ActionHash,
+    },
+    MissingImportParent {
+        entity_type: String,
+        entity: ActionHash,
+        parent_type: String,
+        parent: ActionHash,
+    },
+    InvalidSnapshotSignature {
+        signer: AgentPubKey,
+    },
+    SnapshotSignerMismatch {
+        exported_by: AgentPubKey,
+        signer: AgentPubKey,
+    },
+    SnapshotFromThisDna {
+        dna: DnaHash,
+    },
+    UntrustedSnapshotSigner {
+        signer: AgentPubKey,
+        trusted: Vec<AgentPubKey>,
+    },
+}
+
+impl fmt::Display for AppStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppStoreError::AuthorMismatch { entry_author, action_author } => write!(f,
+                "Entry author does not match Action author: {} != {}",
+                entry_author, action_author,
+            ),
+            AppStoreError::UnauthorizedEditor { role, agent, editors } => match role {
+                AuthorRole::Entry | AuthorRole::Update => write!(f,
+                    "{:?} author ({}) must be in the editors list: {:?}",
+                    role, agent, editors,
+                ),
+                AuthorRole::Delete | AuthorRole::Link => write!(f,
+                    "{:?} author ({}) is not in editor list: {:?}",
+                    role, agent, editors,
+                ),
+            },
+            AppStoreError::EditorsChanged { previous, next } => write!(f,
+                "Cannot update the editors list: {:?} => {:?}",
+                previous, next,
+            ),
+            AppStoreError::ImmutableField { entry_type, field, previous, next } => write!(f,
+                "{} field '{}' cannot be updated: {} => {}",
+                entry_type, field, previous, next,
+            ),
+            AppStoreError::ImmutableEntry { entry_type } => write!(f,
+                "{} cannot be updated",
+                entry_type,
+            ),
+            AppStoreError::DeprecatedEntity => write!(f,
+                "Cannot update deprecated entity unless the deprecation is being reversed",
+            ),
+            AppStoreError::IconTooLarge { entry_type, limit } => write!(f,
+                "{} icon cannot be larger than {}KB ({} bytes)",
+                entry_type, limit/1024, limit,
+            ),
+            AppStoreError::HrlTargetMismatch { target, apphub_hrl_hash } => write!(f,
+                "HRL target does not match 'apphub_hrl_hash': {} != {}",
+                target, apphub_hrl_hash,
+            ),
+            AppStoreError::HrlDnaNotAllowed { dna, allowed } => write!(f,
+                "HRL DNA ({}) is not in the allowed DevHub DNAs: {:?}",
+                dna, allowed,
+            ),
+            AppStoreError::PackageAlreadyClaimed { apphub_hrl_hash, app } => write!(f,
+                "DevHub package ({}) is already claimed by App ({})",
+                apphub_hrl_hash, app,
+            ),
+            AppStoreError::MissingDelegation { author, editors } => write!(f,
+                "Author ({}) is not in the editor list and has no publishing delegation: {:?}",
+                author, editors,
+            ),
+            AppStoreError::DelegationNotApplicable { delegation, author, app, at } => write!(f,
+                "Publishing delegation ({}) does not cover author ({}) for App ({}) at {}",
+                delegation, author, app, at,
+            ),
+            AppStoreError::InvalidDelegationExpiry { issued_at, expires_at, max_lifetime } => write!(f,
+                "Publishing delegation issued at {} must expire after it is issued and within {}ms: {}",
+                issued_at, max_lifetime, expires_at,
+            ),
+            AppStoreError::UnauthorizedDelete { entry_type, author } => write!(f,
+                "Not authorized to delete {} created by {}",
+                entry_type, author,
+            ),
+            AppStoreError::RemovedByGovernance { app } => write!(f,
+                "App ({}) has been removed by the governance group",
+                app,
+            ),
+            AppStoreError::BundleHashMismatch { expected, received } => write!(f,
+                "Received bundle hash does not match expected bundle hash: {} !== {}",
+                received, expected,
+            ),
+            AppStoreError::UnverifiedMirror { mirror, expected } => write!(f,
+                "Mirror ({}) is missing or does not match the bundle hash {}",
+                mirror, expected,
+            ),
+            AppStoreError::UnknownSchemaVersion { version, current } => write!(f,
+                "Schema version {} is newer than the supported version {}",
+                version, current,
+            ),
+            AppStoreError::SchemaDowngrade { previous, next } => write!(f,
+                "Schema version cannot be downgraded: {} => {}",
+                previous, next,
+            ),
+            AppStoreError::OutdatedSchemaVersion { version, current } => write!(f,
+                "New entries must use the current schema version {} (received {})",
+                current, version,
+            ),
+            AppStoreError::InvalidProvenance { dna } => write!(f,
+                "Provenance must reference a previous DNA; not this DNA ({})",
+                dna,
+            ),
+            AppStoreError::InvalidDnaProperties { reason } => write!(f,
+                "Invalid DNA properties: {}",
+                reason,
+            ),
+            AppStoreError::NotGovernanceAgent { agent, governance_agents } => write!(f,
+                "Agent ({}) is not one of the governance agents: {:?}",
+                agent, governance_agents,
+            ),
+            AppStoreError::NotGovernanceGroup { group } => write!(f,
+                "Group ({}) must be created by a governance agent with only governance agents as contributors",
+                group,
+            ),
+            AppStoreError::InvalidModerationSubject { subject } => write!(f,
+                "Moderated subject ({}) must be an App or a Publisher",
+                subject,
+            ),
+            AppStoreError::MissingGroupRevision { group } => write!(f,
+                "Could not find the latest revision of group ({})",
+                group,
+            ),
+            AppStoreError::SubjectMismatch { context, subject, expected } => write!(f,
+                "{} subject does not match: {} != {}",
+                context, subject, expected,
+            ),
+            AppStoreError::GroupMismatch { context, group, expected } => write!(f,
+                "{} group does not match: {} != {}",
+                context, group, expected,
+            ),
+            AppStoreError::NotARemoval { moderator_action, action_type } => write!(f,
+                "Moderator Action ({}) is not a removal: {:?}",
+                moderator_action, action_type,
+            ),
+            AppStoreError::NotGroupContributor { agent, group, revision } => write!(f,
+                "Agent ({}) is not a contributor in Group ({}) revision ({})",
+                agent, group, revision,
+            ),
+            AppStoreError::InvalidLinkBase { link_type, base, expected } => write!(f,
+                "Base address of {} link must be {}; not {}",
+                link_type, expected, base,
+            ),
+            AppStoreError::UnclaimedPackage { apphub_hrl_hash } => write!(f,
+                "No App claims DevHub package ({})",
+                apphub_hrl_hash,
+            ),
+            AppStoreError::MissingVerifiedMirror { app_version } => write!(f,
+                "App Version ({}) has no verified mirror",
+                app_version,
+            ),
+            AppStoreError::SourcePackageMismatch { package_hash, apphub_hrl_hash } => write!(f,
+                "Source ownership package does not match 'apphub_hrl_hash': {} != {}",
+                package_hash, apphub_hrl_hash,
+            ),
+            AppStoreError::NotPackageMaintainer { signer, maintainer } => write!(f,
+                "Source ownership signer ({}) is not the maintainer of the DevHub package: {:?}",
+                signer, maintainer,
+            ),
+            AppStoreError::InvalidSourceOwnershipSignature { signer } => write!(f,
+                "Source ownership signature by {} is not valid for this listing",
+                signer,
+            ),
+            AppStoreError::InvalidRemovedSubjectTag { reason } => write!(f,
+                "Invalid removed subject tag: {}",
+                reason,
+            ),
+            AppStoreError::ManifestSummaryMismatch { ui_hash, happ_hash, bundle_ui_hash, bundle_happ_hash } => write!(f,
+                "Manifest summary hashes (ui: {}, happ: {}) do not match the bundle hashes (ui: {}, happ: {})",
+                ui_hash, happ_hash, bundle_ui_hash, bundle_happ_hash,
+            ),
+            AppStoreError::SerializationFailed { context, reason } => write!(f,
+                "Failed to {}: {}",
+                context, reason,
+            ),
+            AppStoreError::RemoteCallFailed { dna, zome, function, reason } => write!(f,
+                "Remote call to {}::{} on DNA ({}) failed: {}",
+                zome, function, dna, reason,
+            ),
+            AppStoreError::EntryHashMismatch { expected, received } => write!(f,
+                "Hashes do not match: {} !== {}",
+                expected, received,
+            ),
+            AppStoreError::InvalidWebhappBundle { reason } => write!(f,
+                "Failed to inspect webhapp bundle: {}",
+                reason,
+            ),
+            AppStoreError::MissingWebAppResource { path } => write!(f,
+                "WebApp entry is missing resource '{}'",
+                path,
+            ),
+            AppStoreError::AppVersionAppMismatch { from_app, to_app } => write!(f,
+                "App Versions belong to different Apps: {} != {}",
+                from_app, to_app,
+            ),
+            AppStoreError::MissingImportParent { entity_type, entity, parent_type, parent } => write!(f,
+                "{} ({}) of {} ({}) is not in the export",
+                parent_type, parent, entity_type, entity,
+            ),
+            AppStoreError::InvalidSnapshotSignature { signer } => write!(f,
+                "Invalid catalog snapshot signature from agent ({})",
+                signer,
+            ),
+            AppStoreError::SnapshotSignerMismatch { exported_by, signer } => write!(f,
+                "Catalog snapshot was exported by ({}) but signed by ({})",
+                exported_by, signer,
+            ),
+            AppStoreError::SnapshotFromThisDna { dna } => write!(f,
+                "Catalog snapshot was exported from this DNA ({})",
+                dna,
+            ),
+            AppStoreError::UntrustedSnapshotSigner { signer, trusted } => write!(f,
+                "Catalog snapshot signer ({}) is not one of the trusted signers: {:?}",
+                signer, trusted,
+            ),
+        }
+    }
+}
+
+impl AppStoreError {
+    /// The stable code of this error (eg. `UNAUTHORIZED_EDITOR`)
+    pub fn code(&self) -> String {
+        serde_json::to_value( self ).ok()
+            .and_then( |value| value.get("code")?.as_str().map( |code| code.to_string() ) )
+            .unwrap_or_default()
+    }
+
+    /// The readable message followed by the encoded error: `<message> [appstore_error:<hex json>]`
+    pub fn to_message(&self) -> String {
+        let json = serde_json::to_vec( self )
+            .unwrap_or_default();
+        let encoded : String = json.iter()
+            .map( |byte| format!("{:02x}", byte ) )
+            .collect();
+
+        format!("{} [{}{}]", self, APPSTORE_ERROR_MARKER, encoded )
+    }
+
+    /// Recover an error from any text that contains the output of [`AppStoreError::to_message`]
+    pub fn from_message(message: &str) -> Option<Self> {
+        let start = message.find( APPSTORE_ERROR_MARKER )? + APPSTORE_ERROR_MARKER.len();
+        let encoded : String = message[start..].chars()
+            .take_while( |c| c.is_ascii_hexdigit() )
+            .collect();
+        let json = (0..encoded.len()).step_by(2)
+            .map( |i| u8::from_str_radix( encoded.get(i..i+2)?, 16 ).ok() )
+            .collect::<Option<Vec<u8>>>()?;
+
+        serde_json::from_slice( &json ).ok()
+    }
+}
+
+impl From<AppStoreError> for WasmError {
+    fn from(error: AppStoreError) -> Self {
+        wasm_error!(WasmErrorInner::Guest( error.to_message() ))
+    }
+}
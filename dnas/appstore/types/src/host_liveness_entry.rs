@@ -0,0 +1,33 @@
+use hdi::prelude::*;
+
+
+//
+// Host Liveness Entry
+//
+/// This agent's record of how a DevHub host responded to calls of a zome function (a private
+/// entry that never leaves the agent's cell)
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct HostLivenessEntry {
+    pub dna: DnaHash,
+    pub zome: String,
+    pub function: String,
+    pub host: AgentPubKey,
+    pub last_pong: Option<u64>,
+    pub last_failure: Option<u64>,
+    /// Consecutive failures since the last pong
+    pub failures: u32,
+    pub author: AgentPubKey,
+    pub published_at: u64,
+    pub last_updated: u64,
+}
+
+impl HostLivenessEntry {
+    /// Whether this record is for the given host and DNA zome function
+    pub fn is_for(&self, dna: &DnaHash, zome: &str, function: &str, host: &AgentPubKey) -> bool {
+        self.dna == *dna
+            && self.zome == zome
+            && self.function == function
+            && self.host == *host
+    }
+}
@@ -3,14 +3,36 @@ mod app_version_entry;
 mod publisher_entry;
 mod group_anchor_entry;
 mod moderator_entry;
+mod appeal_entry;
+mod viewpoint_preference_entry;
+mod host_liveness_entry;
+mod publishing_delegation_entry;
+mod bundle_verification_entry;
+mod error;
+mod snapshot;
+mod change_log;
+mod signal;
+
+#[cfg(feature = "bundle")]
+pub mod bundle;
 
 pub use coop_content_sdk;
+pub use rmpv;
 
 pub use app_entry::*;
 pub use app_version_entry::*;
 pub use publisher_entry::*;
 pub use group_anchor_entry::*;
 pub use moderator_entry::*;
+pub use appeal_entry::*;
+pub use viewpoint_preference_entry::*;
+pub use host_liveness_entry::*;
+pub use publishing_delegation_entry::*;
+pub use bundle_verification_entry::*;
+pub use error::*;
+pub use snapshot::*;
+pub use change_log::*;
+pub use signal::*;
 
 use std::collections::BTreeMap;
 use hdi::prelude::*;
@@ -29,6 +51,83 @@ pub struct HRL {
     pub target: AnyDhtHash,
 }
 
+/// Where a re-imported entity came from (the previous DNA of a migrated chain)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    pub dna: DnaHash,
+    pub id: EntityId,
+    pub action: ActionHash,
+    pub author: AgentPubKey,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, SerializedBytes)]
+pub struct DnaProperties {
+    /// Agents whose moderator removals apply to every listing (eg. legal takedowns)
+    #[serde(default)]
+    pub governance_agents: Option<Vec<AgentPubKey>>,
+    /// DevHub networks that listings are allowed to point at (any network when unset)
+    #[serde(default)]
+    pub apphub_dnas: Option<Vec<DnaHash>>,
+    /// Externs granted to any caller on init (all of [`READ_ONLY_FUNCTIONS`] when unset); names
+    /// that are not in [`READ_ONLY_FUNCTIONS`] are ignored
+    #[serde(default)]
+    pub read_only_functions: Option<Vec<String>>,
+    /// Agents whose catalog snapshots can be imported (only the importing agent when unset)
+    #[serde(default)]
+    pub snapshot_signers: Option<Vec<AgentPubKey>>,
+}
+
+/// Coordinator externs that only read from the DHT (or hash their input), so any caller can be
+/// granted them
+pub const READ_ONLY_FUNCTIONS : [&str; 41] = [
+    "get_publisher",
+    "get_publishers_for_agent",
+    "get_my_publishers",
+    "get_all_publishers",
+    "get_publisher_moderated_state",
+    "get_publisher_moderator_actions",
+    "get_app",
+    "get_apps_for_agent",
+    "get_apps_for_publisher",
+    "get_my_apps",
+    "get_all_apps",
+    "get_app_for_apphub_hrl",
+    "get_app_version",
+    "get_app_versions_for_app",
+    "get_app_versions_for_dna",
+    "get_app_version_mirrors",
+    "get_moderator_actions",
+    "get_moderated_state",
+    "get_moderation_log",
+    "get_appeals_for_moderator_action",
+    "get_appeal_resolution",
+    "get_open_appeals",
+    "get_governance_agents",
+    "get_governance_group",
+    "get_governance_removed_apps",
+    "get_changes_since",
+    "get_group",
+    "viewpoint_get_all_apps",
+    "viewpoint_get_all_removed_apps",
+    "viewpoint_get_app",
+    "viewpoint_get_apps",
+    "viewpoint_get_publisher",
+    "hash_webapp_package_entry",
+    "hash_webapp_package_version_entry",
+    "hash_webapp_entry",
+    "hash_ui_entry",
+    "hash_app_entry",
+    "hash_mere_memory_entry",
+    "hash_mere_memory_block_entry",
+    "hash_dna_entry",
+    "hash_zome_entry",
+];
+
+/// Check if an extern name is allowed in the unrestricted (read-only) capability grant
+pub fn is_read_only_function(name: &str) -> bool {
+    READ_ONLY_FUNCTIONS.contains( &name )
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DeprecationNotice {
     pub message: String,
@@ -45,3 +144,86 @@ pub trait CommonFields<'a> {
     fn last_updated(&'a self) -> &'a u64;
     fn metadata(&'a self) -> &'a BTreeMap<String, RmpvValue>;
 }
+
+
+/// Entries that record the layout they were written with
+///
+/// Records written before versioning deserialize with `schema_version = 0`; missing fields fall
+/// back to their serde defaults.
+///
+/// ```
+/// use hdi::prelude::*;
+/// use appstore_types::{ PublisherEntry, WebAddress, SchemaVersioned };
+///
+/// // The Publisher layout before metadata, optional fields and versioning were added
+/// #[derive(Debug, serde::Serialize)]
+/// struct OldPublisherEntry {
+///     name: String,
+///     location: String,
+///     website: WebAddress,
+///     editors: Vec<AgentPubKey>,
+///     author: AgentPubKey,
+///     published_at: u64,
+///     last_updated: u64,
+/// }
+///
+/// let author = AgentPubKey::from_raw_36( vec![ 0; 36 ] );
+/// let bytes = encode( &OldPublisherEntry {
+///     name: "Old Publisher".to_string(),
+///     location: "Somewhere".to_string(),
+///     website: WebAddress {
+///         url: "https://example.com".to_string(),
+///         context: None,
+///     },
+///     editors: vec![ author.clone() ],
+///     author: author.clone(),
+///     published_at: 1,
+///     last_updated: 1,
+/// }).unwrap();
+///
+/// let mut entry : PublisherEntry = decode( &bytes ).unwrap();
+///
+/// assert_eq!( entry.schema_version, 0 );
+/// assert!( entry.metadata.is_empty() );
+/// assert!( entry.needs_upgrade() );
+///
+/// entry.upgrade();
+///
+/// assert_eq!( entry.schema_version, PublisherEntry::SCHEMA_VERSION );
+/// assert!( !entry.needs_upgrade() );
+/// ```
+pub trait SchemaVersioned {
+    /// The layout written by this version of the types
+    const SCHEMA_VERSION: u32;
+
+    fn schema_version(&self) -> u32;
+    fn set_schema_version(&mut self, version: u32);
+
+    fn needs_upgrade(&self) -> bool {
+        self.schema_version() < Self::SCHEMA_VERSION
+    }
+
+    /// Mark this entry as using the current layout
+    fn upgrade(&mut self) {
+        self.set_schema_version( Self::SCHEMA_VERSION );
+    }
+}
+
+macro_rules! schema_versioned {
+    ( $type:ident, $version:literal ) => {
+        impl SchemaVersioned for $type {
+            const SCHEMA_VERSION : u32 = $version;
+
+            fn schema_version(&self) -> u32 {
+                self.schema_version
+            }
+            fn set_schema_version(&mut self, version: u32) {
+                self.schema_version = version;
+            }
+        }
+    };
+}
+
+schema_versioned!( PublisherEntry, 2 );
+schema_versioned!( AppEntry, 2 );
+schema_versioned!( AppVersionEntry, 2 );
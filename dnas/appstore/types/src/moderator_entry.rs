@@ -23,3 +23,84 @@ pub struct ModeratorActionEntry {
     pub metadata: BTreeMap<String, RmpvValue>,
 }
 group_ref!( ModeratorActionEntry, group_id );
+
+impl ModeratorActionEntry {
+    /// Derive the typed action from the `remove` flag in this entry's metadata
+    pub fn action_type(&self) -> ModeratorActionType {
+        match self.metadata.get("remove") {
+            Some(RmpvValue::Boolean(true)) => ModeratorActionType::Remove,
+            Some(RmpvValue::Boolean(false)) => ModeratorActionType::Restore,
+            _ => ModeratorActionType::Update,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum ModeratorActionType {
+    Remove,
+    Restore,
+    Update,
+}
+
+
+/// The kind of entity a moderator action (or appeal) is about
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationSubjectType {
+    App,
+    Publisher,
+}
+
+impl ModerationSubjectType {
+    /// The prefix used in '<subject type>::<subject_id>' link tags
+    pub fn tag_prefix(&self) -> &'static str {
+        match self {
+            ModerationSubjectType::App => "app",
+            ModerationSubjectType::Publisher => "publisher",
+        }
+    }
+
+    /// Read the subject type from a '<subject type>::<subject_id>' link tag
+    pub fn from_tag(tag: &[u8]) -> Option<Self> {
+        let tag = String::from_utf8_lossy( tag );
+
+        match tag.split("::").next() {
+            Some("app") => Some( ModerationSubjectType::App ),
+            Some("publisher") => Some( ModerationSubjectType::Publisher ),
+            _ => None,
+        }
+    }
+}
+
+
+//
+// Moderation Audit Log
+//
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModerationLogItem {
+    pub id: ActionHash,
+    pub action: ActionHash,
+    pub address: EntryHash,
+    pub subject_id: ActionHash,
+    pub subject_type: ModerationSubjectType,
+    pub author: AgentPubKey,
+    pub group_rev: ActionHash,
+    pub published_at: u64,
+    pub message: String,
+    pub action_type: ModeratorActionType,
+    pub metadata: BTreeMap<String, RmpvValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, SerializedBytes)]
+pub struct ModerationLogExport {
+    pub group_id: ActionHash,
+    pub exported_at: u64,
+    pub items: Vec<ModerationLogItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModerationLogVerification {
+    pub verified: bool,
+    pub mismatched: Vec<ActionHash>,
+    pub missing: Vec<ActionHash>,
+}
@@ -1,4 +1,5 @@
 use crate::{
+    Provenance,
     RmpvValue,
     CommonFields,
     DeprecationNotice,
@@ -35,10 +36,21 @@ pub struct PublisherEntry {
     pub metadata: BTreeMap<String, RmpvValue>,
 
     // optional
+    #[serde(default)]
     pub description: Option<String>,
+    #[serde(default)]
     pub email: Option<String>,
+    #[serde(default)]
     pub icon: Option<EntryHash>,
+    #[serde(default)]
     pub deprecation: Option<DeprecationNotice>,
+    /// Set when the entity was imported from a previous DNA
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+
+    /// See [`SchemaVersioned`](crate::SchemaVersioned)
+    #[serde(default)]
+    pub schema_version: u32,
 }
 
 impl<'a> CommonFields<'a> for PublisherEntry {
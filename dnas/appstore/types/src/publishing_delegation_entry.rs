@@ -0,0 +1,65 @@
+use crate::{
+    EntityId,
+    RmpvValue,
+    CommonFields,
+};
+use std::collections::BTreeMap;
+use hdi::prelude::*;
+
+
+//
+// Publishing Delegation Entry
+//
+/// Longest time (in ms) that a publishing delegation can be valid for
+///
+/// Validation cannot observe a revoked (deleted) delegation, so this bounds how long a revoked
+/// delegation can still be used.
+pub const MAX_DELEGATION_LIFETIME : u64 = 7 * 24 * 60 * 60 * 1_000;
+
+/// An App editor's grant allowing another agent (eg. a CI bot) to publish versions of one App
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct PublishingDelegationEntry {
+    pub for_app: EntityId,
+    pub delegate: AgentPubKey,
+
+    // common fields
+    pub author: AgentPubKey,
+    pub published_at: u64,
+    pub last_updated: u64,
+    pub metadata: BTreeMap<String, RmpvValue>,
+    /// Versions created or updated (by action timestamp) at or after this time are not covered
+    pub expires_at: u64,
+}
+
+impl PublishingDelegationEntry {
+    /// Whether this delegation (issued at `issued_at`) covers a version of the given App written
+    /// at the given time
+    pub fn covers(
+        &self,
+        for_app: &EntityId,
+        delegate: &AgentPubKey,
+        issued_at: u64,
+        at: u64,
+    ) -> bool {
+        self.for_app == *for_app
+            && self.delegate == *delegate
+            && issued_at <= at
+            && at < self.expires_at
+    }
+}
+
+impl<'a> CommonFields<'a> for PublishingDelegationEntry {
+    fn author(&'a self) -> &'a AgentPubKey {
+	&self.author
+    }
+    fn published_at(&'a self) -> &'a u64 {
+	&self.published_at
+    }
+    fn last_updated(&'a self) -> &'a u64 {
+	&self.last_updated
+    }
+    fn metadata(&'a self) -> &'a BTreeMap<String, RmpvValue> {
+	&self.metadata
+    }
+}
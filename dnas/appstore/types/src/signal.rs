@@ -0,0 +1,45 @@
+use crate::{
+    EntityId,
+    ChangeType,
+    PublisherEntry,
+    AppEntry,
+    AppVersionEntry,
+    ModeratorActionEntry,
+};
+use hdi::prelude::*;
+
+
+//
+// Local Signals
+//
+/// Emitted by the coordinator's `post_commit` for each store change made by this cell
+///
+/// `content` is the new entry (`None` for deletes).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum AppStoreSignal {
+    Publisher {
+        change_type: ChangeType,
+        id: EntityId,
+        action: ActionHash,
+        content: Option<PublisherEntry>,
+    },
+    App {
+        change_type: ChangeType,
+        id: EntityId,
+        action: ActionHash,
+        content: Option<AppEntry>,
+    },
+    AppVersion {
+        change_type: ChangeType,
+        id: EntityId,
+        action: ActionHash,
+        content: Option<AppVersionEntry>,
+    },
+    ModeratorAction {
+        change_type: ChangeType,
+        id: EntityId,
+        action: ActionHash,
+        content: Option<ModeratorActionEntry>,
+    },
+}
@@ -0,0 +1,53 @@
+use crate::{
+    PublisherEntry,
+    AppEntry,
+    AppVersionEntry,
+    ModerationLogExport,
+};
+use hc_crud::{
+    Entity,
+};
+use hdi::prelude::*;
+
+
+/// A memory (eg. an icon) referenced by exported entities
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MigrationMemory {
+    pub address: EntryHash,
+    pub bytes: SerializedBytes,
+}
+
+
+//
+// Catalog Snapshot
+//
+/// Default number of entities in one page of a catalog snapshot
+pub const CATALOG_SNAPSHOT_PAGE_SIZE : u32 = 50;
+
+/// One page of every Publisher, App and App Version of a network
+///
+/// Entities are ordered Publishers, then Apps, then App Versions so that a parent is never in a
+/// later page than its children.  The first page also carries the moderation history of the
+/// requested groups and each page carries the icons of its own entities.
+#[derive(Debug, Serialize, Deserialize, Clone, SerializedBytes)]
+pub struct CatalogSnapshot {
+    pub dna: DnaHash,
+    pub exported_at: u64,
+    pub exported_by: AgentPubKey,
+    pub page: u32,
+    /// There are more entities after this page
+    pub more: bool,
+    pub publishers: Vec<Entity<PublisherEntry>>,
+    pub apps: Vec<Entity<AppEntry>>,
+    pub app_versions: Vec<Entity<AppVersionEntry>>,
+    pub moderation_logs: Vec<ModerationLogExport>,
+    pub memories: Vec<MigrationMemory>,
+}
+
+/// A msgpack encoded [`CatalogSnapshot`] and the exporting agent's signature over those bytes
+#[derive(Debug, Serialize, Deserialize, Clone, SerializedBytes)]
+pub struct SignedCatalogSnapshot {
+    pub snapshot: SerializedBytes,
+    pub signer: AgentPubKey,
+    pub signature: Signature,
+}
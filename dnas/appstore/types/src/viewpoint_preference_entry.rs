@@ -0,0 +1,14 @@
+use hdi::prelude::*;
+
+
+//
+// Viewpoint Preference Entry
+//
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct ViewpointPreferenceEntry {
+    pub group_ids: Vec<ActionHash>,
+    pub author: AgentPubKey,
+    pub published_at: u64,
+    pub last_updated: u64,
+}
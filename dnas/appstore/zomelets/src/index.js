@@ -1,6 +1,6 @@
 import {
     AnyDhtHash,
-    AgentPubKey,
+    AgentPubKey, DnaHash,
     ActionHash, EntryHash,
 }					from '@spartan-hc/holo-hash'; // approx. 11kb
 import {
@@ -141,6 +141,11 @@ export const AppStoreCSRZomelet		= new Zomelet({
 
 	return new App( result, this );
     },
+    async get_app_for_apphub_hrl ( input ) {
+	const result			= await this.call( new EntryHash( input ) );
+
+	return new App( result, this );
+    },
     async get_apps_for_publisher ( input ) {
 	const result			= await this.call( input );
 
@@ -161,6 +166,16 @@ export const AppStoreCSRZomelet		= new Zomelet({
 
 	return result.map( app => new App( app, this ) );
     },
+    async get_governance_removed_apps ( input ) {
+	const result			= await this.call( input );
+
+	return result.map( app => new App( app, this ) );
+    },
+    async my_view_get_all_apps ( input ) {
+	const result			= await this.call( input );
+
+	return result.map( app => new App( app, this ) );
+    },
     async update_app ( input ) {
 	if ( input.properties.icon && input.properties.icon.length > 39 )
 	    input.properties.icon	= await this.zomes.mere_memory_api.save( input.properties.icon );
@@ -221,11 +236,28 @@ export const AppStoreCSRZomelet		= new Zomelet({
 
 	return versions;
     },
+    async get_app_versions_for_dna ( input ) {
+	const result			= await this.call( new DnaHash( input ) );
+
+	return result.map( item => {
+	    return {
+		"app": new App( item.app, this ),
+		"app_version": new AppVersion( item.app_version, this ),
+	    };
+	});
+    },
+    "verify_app_version":		true,
+    "get_bundle_verifications":		true,
     async update_app_version ( input ) {
 	const result			= await this.call( input );
 
 	return new AppVersion( result, this );
     },
+    async delete_app_version ( input ) {
+	const result			= await this.call( input );
+
+	return new ActionHash( result );
+    },
 
     //
     // Group
@@ -265,6 +297,80 @@ export const AppStoreCSRZomelet		= new Zomelet({
     "update_moderated_state":		true,
     "viewpoint_get_all_apps":		true,
     "viewpoint_get_all_removed_apps":	true,
+    "backfill_removed_subject_index":	true,
+    "backfill_apphub_hrl_claims":	true,
+    "get_moderation_log":		true,
+    "export_moderation_log":		true,
+    "verify_moderation_log_export":	true,
+
+    "create_appeal":			true,
+    "get_appeals_for_moderator_action":	true,
+    "get_appeal_resolution":		true,
+    "get_open_appeals":			true,
+    "resolve_appeal":			true,
+
+    "get_read_only_functions":		true,
+
+    "get_governance_agents":		true,
+    "get_governance_group":		true,
+    "register_governance_group":	true,
+
+    "sign_source_ownership_claim":	true,
+    "inspect_webhapp_bundle":		true,
+    "get_app_version_download_manifest": true,
+    "get_app_version_delta":		true,
+
+    "record_host_response":		true,
+    "get_ranked_hosts":			true,
+
+    "issue_publishing_delegation":	true,
+    "get_publishing_delegations":	true,
+    "revoke_publishing_delegation":	true,
+
+    "upgrade_my_publishers":		true,
+    "upgrade_my_apps":			true,
+    "upgrade_my_app_versions":		true,
+    "upgrade_my_entries":		true,
+
+    "export_my_entries":		true,
+    "close_my_chain":			true,
+    "open_my_chain":			true,
+    "import_my_entries":		true,
+    "export_catalog_snapshot":		true,
+    "import_catalog_snapshot":		true,
+    "get_changes_since":		true,
+
+    "mirror_app_version":		true,
+    "get_app_version_mirrors":		true,
+    "get_app_version_bundle":		true,
+
+    async viewpoint_get_apps ( input ) {
+	const result			= await this.call( input );
+
+	result.items			= result.items.map( app => new App( app, this ) );
+
+	return result;
+    },
+    async viewpoint_get_app ( input ) {
+	const result			= await this.call( input );
+
+	result.entity			= new App( result.entity, this );
+
+	return result;
+    },
+    async viewpoint_get_publisher ( input ) {
+	const result			= await this.call( input );
+
+	result.entity			= new Publisher( result.entity, this );
+
+	return result;
+    },
+    "get_publisher_moderator_actions":	true,
+    "get_publisher_moderated_state":	true,
+    "update_publisher_moderated_state":	true,
+
+    "get_my_viewpoint":			true,
+    "set_my_viewpoint":			true,
 
 
     //
@@ -47,6 +47,7 @@ export const PublisherStruct = {
     "email":			OptionType( String ),
     "icon":			OptionType( EntryHash ),
     "deprecation":		OptionType( DeprecationNoticeStruct ),
+    "schema_version":		Number,
 };
 
 export function PublisherEntry ( entry ) {
@@ -92,6 +93,12 @@ export class Publisher extends ScopedEntity {
 
 
 
+export const SourceOwnershipProofStruct = {
+    "signer":			AgentPubKey,
+    "signature":		Uint8Array,
+    "package":			AnyType,
+};
+
 export const AppStruct = {
     "title":			String,
     "subtitle":			String,
@@ -110,6 +117,8 @@ export const AppStruct = {
     // optional
     "icon":			OptionType( EntryHash ),
     "deprecation":		OptionType( DeprecationNoticeStruct ),
+    "source_ownership":		OptionType( SourceOwnershipProofStruct ),
+    "schema_version":		Number,
 };
 
 export function AppEntry ( entry ) {
@@ -207,6 +216,40 @@ export const BundleHashesStruct = {
     "happ_hash":		String,
 };
 
+export const BundleVerificationStruct = {
+    "app_version":		ActionHash,
+    "bundle_hashes":		BundleHashesStruct,
+    "verified":			Boolean,
+    "author":			AgentPubKey,
+    "published_at":		Number,
+};
+
+export function BundleVerificationEntry ( entry ) {
+    return intoStruct( entry, BundleVerificationStruct );
+}
+
+export const ZomeSummaryStruct = {
+    "name":			String,
+    "wasm_sha256":		String,
+};
+
+export const RoleSummaryStruct = {
+    "name":			String,
+    "dna_name":			String,
+    "installed_hash":		OptionType( String ),
+    "network_seed":		OptionType( String ),
+    "integrity_zomes":		VecType( ZomeSummaryStruct ),
+};
+
+export const ManifestSummaryStruct = {
+    "name":			String,
+    "happ_name":		String,
+    "happ_description":		OptionType( String ),
+    "ui_hash":			String,
+    "happ_hash":		String,
+    "roles":			VecType( RoleSummaryStruct ),
+};
+
 export const AppVersionStruct = {
     "version":			String,
     "for_app":			ActionHash,
@@ -219,6 +262,12 @@ export const AppVersionStruct = {
     "published_at":		Number,
     "last_updated":		Number,
     "metadata":			MapType( String, AnyType ),
+
+    // optional
+    "manifest":			OptionType( ManifestSummaryStruct ),
+    "dna_hashes":		OptionType( VecType( DnaHash ) ),
+    "delegation":		OptionType( ActionHash ),
+    "schema_version":		Number,
 };
 
 export function AppVersionEntry ( entry ) {
@@ -239,6 +288,18 @@ export class AppVersion extends ScopedEntity {
 	return this;
     }
 
+    async $verify () {
+	return await this.zome.verify_app_version({
+	    "id": this.$id,
+	});
+    }
+
+    async $getBundleVerifications () {
+	return await this.zome.get_bundle_verifications({
+	    "id": this.$id,
+	});
+    }
+
     //
     // "apphub" methods
     //
@@ -294,6 +355,28 @@ export class Group extends ScopedEntity {
 	});
     }
 
+    async $getModerationLog () {
+	return await this.zome.get_moderation_log( this.$id );
+    }
+
+    async $exportModerationLog () {
+	return await this.zome.export_moderation_log( this.$id );
+    }
+
+    async $getApp ( app_id ) {
+	return await this.zome.viewpoint_get_app({
+	    "group_id": this.$id,
+	    "id": app_id,
+	});
+    }
+
+    async $getPublisher ( publisher_id ) {
+	return await this.zome.viewpoint_get_publisher({
+	    "group_id": this.$id,
+	    "id": publisher_id,
+	});
+    }
+
     async $getAppModeratedActions ( app_id ) {
 	return await this.zome.get_moderator_actions({
 	    "group_id": this.$id,
@@ -301,6 +384,18 @@ export class Group extends ScopedEntity {
 	});
     }
 
+    async $getOpenAppeals () {
+	return await this.zome.get_open_appeals( this.$id );
+    }
+
+    async $resolveAppeal ( appeal_id, message, restore ) {
+	return await this.zome.resolve_appeal({
+	    "appeal": appeal_id,
+	    message,
+	    restore,
+	});
+    }
+
     async $removeApp ( app_id, message ) {
 	const ma_state		= await this.$getAppModeratedState( app_id );
 	const metadata		= Object.assign( {}, ma_state?.metastate, {
@@ -332,6 +427,47 @@ export class Group extends ScopedEntity {
 
 
 
+export const APPSTORE_ERROR_MARKER	= "appstore_error:";
+
+// Hash fields arrive as byte arrays (39 bytes)
+function revive_hashes ( value ) {
+    if ( Array.isArray( value ) ) {
+	if ( value.length === 39 && value.every( n => Number.isInteger( n ) ) )
+	    return new HoloHash( new Uint8Array( value ) );
+
+	return value.map( revive_hashes );
+    }
+
+    if ( value !== null && typeof value === "object" ) {
+	for ( let [k, v] of Object.entries( value ) )
+	    value[k]			= revive_hashes( v );
+    }
+
+    return value;
+}
+
+/**
+ * Recover the structured AppStoreError ({ code, ...fields }) from a zome call error
+ *
+ * Returns null when the error does not carry an AppStoreError.
+ */
+export function parseAppStoreError ( error ) {
+    const message			= String( error?.message ?? error );
+    const match				= message.match( /appstore_error:([0-9a-f]+)/ );
+
+    if ( match === null )
+	return null;
+
+    const hex				= match[1];
+    const bytes				= new Uint8Array( hex.length / 2 );
+
+    for ( let i = 0; i < bytes.length; i++ )
+	bytes[i]			= parseInt( hex.slice( i*2, i*2 + 2 ), 16 );
+
+    return revive_hashes( JSON.parse( new TextDecoder().decode( bytes ) ) );
+}
+
+
 export default {
     PublisherStruct,
     PublisherEntry,
@@ -64,6 +64,7 @@ let bobby_client;
 let carol_client;
 
 let alice_appstore_csr;
+let alice_mere_memory_api;
 let alice_portal_csr;
 
 let bobby_zomehub_csr;
@@ -171,6 +172,7 @@ describe("App Store + DevHub", () => {
 	    })
 
 	    alice_appstore_csr		= appstore.zomes.appstore_csr.functions;
+	    alice_mere_memory_api	= appstore.zomes.mere_memory_api.functions;
 	    alice_portal_csr		= portal.zomes.portal_csr.functions;
 	}
 
@@ -231,6 +233,7 @@ let pack_v1;
 let version_v1;
 let app_v1;
 let app_version_v1;
+let app_version_v3;
 
 async function setup () {
     src_bundle				= Bundle.createWebhapp({
@@ -414,6 +417,60 @@ function download_tests () {
 	expect( versions		).to.have.length( 1 );
     });
 
+    it("should record a bundle verification attestation", async function () {
+	this.timeout( 60_000 );
+
+	const verification		= await app_version_v1.$verify();
+
+	log.normal("Bundle verification: %s", json.debug(verification) );
+
+	expect( verification.verified	).to.be.true;
+	expect( String(verification.app_version) ).to.equal( String(app_version_v1.$id) );
+
+	const verifications		= await app_version_v1.$getBundleVerifications();
+	const { pubkey }		= await alice_appstore_csr.whoami();
+
+	expect( verifications		).to.have.length( 1 );
+	expect( String(verifications[0].author) ).to.equal( String(pubkey.initial) );
+
+	// The App Version entry no longer carries a shared verification flag
+	const app_version		= await alice_appstore_csr.get_app_version( app_version_v1.$id );
+
+	expect( app_version		).to.not.have.property("bundle_verification");
+    });
+
+    it("should get download manifest with every DevHub resource and block", async function () {
+	this.timeout( 60_000 );
+
+	const manifest			= await alice_appstore_csr.get_app_version_download_manifest({
+	    "id":		app_version_v1.$id,
+	    "dnahub_dna":	bobby_client.roles.dnahub,
+	    "zomehub_dna":	bobby_client.roles.zomehub,
+	});
+
+	log.normal("App Version download manifest: %s", json.debug(manifest) );
+
+	const types			= manifest.resources.map( resource => resource.resource_type );
+
+	expect( types			).to.include.members([ "Ui", "Happ", "Dna", "Zome" ]);
+	expect(
+	    types.filter( type => type === "Dna" )
+	).to.have.length( src_happ_bundle.manifest.roles.length );
+
+	for ( let resource of manifest.resources ) {
+	    if ( [ "Ui", "Zome" ].includes( resource.resource_type ) ) {
+		expect( resource.memory		).to.not.be.null;
+		expect( resource.memory.blocks	).to.have.length.above( 0 );
+
+		const total		= resource.memory.blocks.reduce( (sum, block) => sum + block.size, 0 );
+
+		expect( total			).to.equal( resource.memory.memory_size );
+	    }
+	    else
+		expect( resource.memory		).to.be.null;
+	}
+    });
+
     it("should download DevHub webapp package", async function () {
 	this.timeout( 120_000 );
 
@@ -554,6 +611,135 @@ function download_tests () {
 	expect( src_msgpack_hash	).to.equal( new_msgpack_hash );
     });
 
+    it("should get an empty delta between an App Version and itself", async function () {
+	this.timeout( 60_000 );
+
+	const delta			= await alice_appstore_csr.get_app_version_delta({
+	    "from":		app_version_v1.$id,
+	    "to":		app_version_v1.$id,
+	    "dnahub_dna":	bobby_client.roles.dnahub,
+	    "zomehub_dna":	bobby_client.roles.zomehub,
+	});
+
+	log.normal("App Version delta: %s", json.debug(delta) );
+
+	expect( delta.changed_resources	).to.have.length( 0 );
+	expect( delta.download_size	).to.equal( 0 );
+	expect(
+	    delta.memories.map( memory => memory.resource_type )
+	).to.include.members([ "Ui", "Zome" ]);
+
+	for ( let memory of delta.memories )
+	    expect( memory.changed_blocks	).to.have.length( 0 );
+    });
+
+    it("should only list the changed UI blocks in an App Version delta", async function () {
+	this.timeout( 120_000 );
+
+	const ui_bytes			= new Uint8Array( Array( 1_000 ).fill( 2 ) );
+	const bundle			= Bundle.createWebhapp({
+	    "name": "fake-webhapp-2",
+	    "ui": {
+		"bytes": ui_bytes,
+	    },
+	    "happ_manifest": {
+		"bytes": await fs.readFile( APPSTORE_PATH ),
+	    },
+	});
+	const webapp_v2			= await bobby_apphub_csr.save_webapp( bundle.toBytes() );
+	const version_v3		= await bobby_apphub_csr.create_webapp_package_version({
+	    "version": "0.3.0",
+	    "for_package": pack_v1.$id,
+	    "webapp": webapp_v2.$addr,
+	    "source_code_revision_uri": faker.internet.url(),
+	});
+	app_version_v3			= await alice_appstore_csr.create_app_version( createAppVersionInput({
+	    "version": version_v3.version,
+	    "for_app": app_v1.$id,
+	    "apphub_hrl": {
+		"dna": bobby_client.roles.apphub,
+		"target": version_v3.$id,
+	    },
+	    "apphub_hrl_hash": version_v3.$addr,
+	}) );
+
+	const delta			= await alice_appstore_csr.get_app_version_delta({
+	    "from":		app_version_v1.$id,
+	    "to":		app_version_v3.$id,
+	    "dnahub_dna":	bobby_client.roles.dnahub,
+	    "zomehub_dna":	bobby_client.roles.zomehub,
+	});
+
+	log.normal("App Version delta: %s", json.debug(delta) );
+
+	const ui_memory			= delta.memories.find( memory => memory.resource_type === "Ui" );
+
+	expect( ui_memory.changed_blocks	).to.have.length.above( 0 );
+	expect( delta.download_size	).to.equal(
+	    ui_memory.changed_blocks.reduce( (sum, block) => sum + block.size, 0 )
+	);
+	expect(
+	    delta.changed_resources.map( resource => resource.resource_type )
+	).to.have.members([ "Ui" ]);
+
+	// The hApp is unchanged so every zome block is already downloaded
+	for ( let memory of delta.memories.filter( memory => memory.resource_type === "Zome" ) ) {
+	    expect( memory.changed_blocks	).to.have.length( 0 );
+	    expect( memory.unchanged_blocks	).to.have.length.above( 0 );
+	}
+    });
+
+    it("should mirror an App Version bundle", async function () {
+	this.timeout( 60_000 );
+
+	const address			= await alice_appstore_csr.mirror_app_version({
+	    "id":			app_version_v1.$id,
+	    "address":			null,
+	});
+	const mirrors			= await alice_appstore_csr.get_app_version_mirrors({
+	    "id":			app_version_v1.$id,
+	});
+
+	log.normal("App Version mirrors: %s", json.debug(mirrors) );
+
+	expect( mirrors			).to.have.length( 1 );
+	expect( String(new EntryHash( mirrors[0] )) ).to.equal( String(new EntryHash( address )) );
+    });
+
+    it("should reject a poisoned mirror", async function () {
+	this.timeout( 60_000 );
+
+	// A memory that claims the bundle hash but holds other bytes
+	const bytes			= crypto.randomBytes( 1_000 );
+	const block_addr		= await alice_mere_memory_api.create_memory_block_entry({
+	    "sequence": {
+		"position":		1,
+		"length":		1,
+	    },
+	    bytes,
+	});
+	const poisoned_addr		= await alice_mere_memory_api.create_memory_entry({
+	    "hash":			app_version_v3.bundle_hashes.hash,
+	    "compression":		null,
+	    "uncompressed_size":	null,
+	    "memory_size":		bytes.length,
+	    "block_addresses":		[ block_addr ],
+	});
+
+	await expect_reject( async () => {
+	    await alice_appstore_csr.mirror_app_version({
+		"id":			app_version_v3.$id,
+		"address":		poisoned_addr,
+	    });
+	}, "does not match the bundle hash" );
+
+	const mirrors			= await alice_appstore_csr.get_app_version_mirrors({
+	    "id":			app_version_v3.$id,
+	});
+
+	expect( mirrors			).to.have.length( 0 );
+    });
+
     it("should get DevHub webapp asset", async function () {
 	this.timeout( 60_000 );
 
@@ -664,6 +850,64 @@ function errors_tests () {
 	expect( target_hash		).to.deep.not.equal( bad_hash );
     });
 
+    it("should reject forged source ownership proofs", async function () {
+	this.timeout( 30_000 );
+
+	const webapp_package		= await alice_appstore_csr.call_apphub_zome_function({
+	    "dna":		app_v1.apphub_hrl.dna,
+	    "zome":		"apphub_csr",
+	    "function":		"get_webapp_package",
+	    "args":		app_v1.apphub_hrl.target,
+	});
+	const { pubkey }		= await alice_appstore_csr.whoami();
+	const maintainer		= new AgentPubKey( webapp_package.maintainer.Agent );
+	const forged_signature		= crypto.randomBytes( 64 );
+
+	// Alice is not the package maintainer
+	await expect_reject( async () => {
+	    await alice_appstore_csr.update_app({
+		"base": app_v1.$action,
+		"properties": {
+		    "source_ownership": {
+			"signer":	pubkey.initial,
+			"signature":	forged_signature,
+			"package":	webapp_package,
+		    },
+		},
+	    });
+	}, "is not the maintainer of the DevHub package" );
+
+	// Claiming to be the maintainer without their signature
+	await expect_reject( async () => {
+	    await alice_appstore_csr.update_app({
+		"base": app_v1.$action,
+		"properties": {
+		    "source_ownership": {
+			"signer":	maintainer,
+			"signature":	forged_signature,
+			"package":	webapp_package,
+		    },
+		},
+	    });
+	}, "is not valid for this listing" );
+
+	// A package that names Alice as maintainer is not the listed package
+	await expect_reject( async () => {
+	    await alice_appstore_csr.update_app({
+		"base": app_v1.$action,
+		"properties": {
+		    "source_ownership": {
+			"signer":	pubkey.initial,
+			"signature":	forged_signature,
+			"package":	Object.assign( {}, webapp_package, {
+			    "maintainer": { "Agent": pubkey.initial },
+			}),
+		    },
+		},
+	    });
+	}, "Source ownership package does not match 'apphub_hrl_hash'" );
+    });
+
     it("should fail because all hosts were unreachable", async function () {
 	this.timeout( 60_000 );
 
@@ -677,4 +921,21 @@ function errors_tests () {
 	    });
 	}, "hosts are unavailable");
     });
+
+    it("should get bundle from the verified mirror when all hosts are unreachable", async function () {
+	this.timeout( 60_000 );
+
+	const bundle_bytes		= await alice_appstore_csr.get_app_version_bundle({
+	    "id":		app_version_v1.$id,
+	});
+	const bundle			= new Bundle( bundle_bytes, "webhapp" );
+
+	expect( bundle.manifest.name	).to.equal( src_bundle.manifest.name );
+
+	await expect_reject( async () => {
+	    await alice_appstore_csr.get_app_version_bundle({
+		"id":		app_version_v3.$id,
+	    });
+	}, "has no verified mirror" );
+    });
 }
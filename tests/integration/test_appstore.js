@@ -16,13 +16,12 @@ import {
 import { Holochain }			from '@spartan-hc/holochain-backdrop';
 
 import {
-    AppStoreCell,
+    parseAppStoreError,
 }					from '@holochain/appstore-zomelets';
-import {
-    AppInterfaceClient,
-}					from '@spartan-hc/app-interface-client';
 
 import {
+    EMPTY_BUNDLE_HASHES,
+    setupAppStore,
     expect_reject,
     linearSuite,
     createAppInput,
@@ -34,10 +33,7 @@ import {
 const __dirname				= path.dirname( new URL(import.meta.url).pathname );
 const APPSTORE_DNA_PATH			= path.join( __dirname, "../../dnas/appstore.dna" );
 
-let app_port;
-let client;
-let alice_client
-let bobby_client;
+let alice_client;
 
 let appstore_csr;
 let bobby_appstore_csr;
@@ -52,54 +48,23 @@ describe("Appstore", () => {
     before(async function () {
 	this.timeout( 60_000 );
 
-	const installations		= await holochain.install([
+	const {
+	    alice,
+	    bobby,
+	}				= await setupAppStore( holochain, APPSTORE_DNA_PATH, [
 	    "alice",
 	    "bobby",
-	], [
-	    {
-		"app_name": "test",
-		"bundle": {
-		    "appstore":	APPSTORE_DNA_PATH,
-		},
-	    },
 	]);
 
-	app_port			= await holochain.ensureAppPort();
-
-	client				= new AppInterfaceClient( app_port, {
-	    "logging": process.env.LOG_LEVEL || "normal",
-	});
-
-	const alice_token		= installations.alice.test.auth.token;
-	alice_client			= await client.app( alice_token );
-
-	const bobby_token		= installations.bobby.test.auth.token;
-	bobby_client			= await client.app( bobby_token );
-
-	{
-	    const {
-		appstore,
-	    }				= alice_client.createInterface({
-		"appstore":	AppStoreCell,
-	    });
-
-	    appstore_csr		= appstore.zomes.appstore_csr.functions;
-	}
-
-	{
-	    const bobby_appstore	= bobby_client.createCellInterface( "appstore", AppStoreCell );
-
-	    bobby_appstore_csr		= bobby_appstore.zomes.appstore_csr.functions;
-	}
-
-	// Must call whoami on each cell to ensure that init has finished.
-	await appstore_csr.whoami();
-	await bobby_appstore_csr.whoami();
+	alice_client			= alice.client;
+	appstore_csr			= alice.appstore_csr;
+	bobby_appstore_csr		= bobby.appstore_csr;
     });
 
     linearSuite("Publisher", publisher_tests.bind( this, holochain ) );
     linearSuite("App", app_tests.bind( this, holochain ) );
     linearSuite("App Version", app_version_tests.bind( this, holochain ) );
+    linearSuite("Host Liveness", host_liveness_tests.bind( this, holochain ) );
     linearSuite("Errors", errors_tests.bind( this, holochain ) );
 
     after(async () => {
@@ -110,6 +75,8 @@ describe("Appstore", () => {
 
 
 let publisher1;
+let delegation1;
+let delegated_version;
 
 function publisher_tests () {
 
@@ -213,6 +180,40 @@ function app_tests () {
 	expect( app.$id			).to.deep.equal( app1.$id );
     });
 
+    it("should get app for DevHub package", async function () {
+	const app			= await appstore_csr.get_app_for_apphub_hrl( app1.apphub_hrl_hash );
+
+	expect( app.$id			).to.deep.equal( app1.$id );
+    });
+
+    it("should move DevHub package claim when the package changes", async function () {
+	this.timeout( 10_000 );
+
+	const previous_hash		= app1.apphub_hrl_hash;
+	const { apphub_hrl,
+		apphub_hrl_hash }	= createAppInput();
+
+	await app1.$update({
+	    apphub_hrl,
+	    apphub_hrl_hash,
+	});
+
+	{
+	    const claimed		= await appstore_csr.get_app_for_apphub_hrl( apphub_hrl_hash );
+	    expect( claimed.$id		).to.deep.equal( app1.$id );
+	}
+
+	await expect_reject( async () => {
+	    await appstore_csr.get_app_for_apphub_hrl( previous_hash );
+	}, "No App claims DevHub package" );
+    });
+
+    it("should not backfill DevHub package claims that exist", async function () {
+	const count			= await appstore_csr.backfill_apphub_hrl_claims();
+
+	expect( count			).to.equal( 0 );
+    });
+
     it("should get apps for an agent", async function () {
 	const apps			= await appstore_csr.get_apps_for_agent({
 	    "for_agent": alice_client.agent_id,
@@ -288,12 +289,27 @@ function app_version_tests () {
 		"ui_hash": "",
 		"happ_hash": "",
 	    },
+	    "dna_hashes": [
+		new DnaHash( crypto.randomBytes(32) ),
+	    ],
 	});
 	app_version1			= await appstore_csr.create_app_version( input );
 
 	log.normal("%s", json.debug( app_version1 ) );
     });
 
+    it("should get app versions for DNA", async function () {
+	this.timeout( 10_000 );
+
+	const results			= await appstore_csr.get_app_versions_for_dna(
+	    app_version1.dna_hashes[0]
+	);
+
+	expect( results			).to.have.length( 1 );
+	expect( results[0].app.$id	).to.deep.equal( app1.$id );
+	expect( results[0].app_version.$id ).to.deep.equal( app_version1.$id );
+    });
+
     it("should get versions for app", async function () {
 	this.timeout( 10_000 );
 
@@ -312,6 +328,180 @@ function app_version_tests () {
 	expect( app_version1.version	).to.equal( "0.1.1" );
     });
 
+    it("should get changes since a timestamp", async function () {
+	this.timeout( 10_000 );
+
+	const page			= await appstore_csr.get_changes_since({
+	    "since": 0,
+	});
+	const version_changes		= page.changes.filter(
+	    change => String( new ActionHash( change.subject_id ) ) === String( app_version1.$id )
+	);
+
+	expect( version_changes.map( change => change.change_type ) ).to.deep.equal([ "Create", "Update" ]);
+
+	const first_page		= await appstore_csr.get_changes_since({
+	    "since": 0,
+	    "limit": 1,
+	});
+
+	expect( first_page.changes	).to.have.length( 1 );
+	expect( first_page.more		).to.be.true;
+
+	const next_page			= await appstore_csr.get_changes_since({
+	    "since": first_page.changes[0].timestamp,
+	    "after": first_page.changes[0].action,
+	});
+
+	expect( next_page.changes	).to.have.length( page.changes.length - 1 );
+    });
+
+    it("should have nothing to upgrade", async function () {
+	this.timeout( 10_000 );
+
+	expect( app_version1.schema_version ).to.equal( 2 );
+
+	const report			= await appstore_csr.upgrade_my_entries();
+
+	expect( report.publishers	).to.have.length( 0 );
+	expect( report.apps		).to.have.length( 0 );
+	expect( report.app_versions	).to.have.length( 0 );
+    });
+
+    it("should publish version with a delegation", async function () {
+	this.timeout( 20_000 );
+
+	const bobby_info		= await bobby_appstore_csr.whoami();
+	delegation1			= await appstore_csr.issue_publishing_delegation({
+	    "for_app": app1.$id,
+	    "delegate": new AgentPubKey( bobby_info.agent_initial_pubkey ),
+	});
+
+	// Delegations always expire
+	expect( delegation1.content.expires_at ).to.be.above( Date.now() );
+
+	const input			= createAppVersionInput({
+	    "version": "0.2.0",
+	    "for_app": app1.$id,
+	    "bundle_hashes": EMPTY_BUNDLE_HASHES,
+	});
+	delegated_version		= await bobby_appstore_csr.create_app_version( input );
+
+	expect( delegated_version.delegation ).to.deep.equal( new ActionHash( delegation1.id ) );
+    });
+
+    it("should let a delegate update their own version", async function () {
+	this.timeout( 20_000 );
+
+	const app_version		= await bobby_appstore_csr.update_app_version({
+	    "base": delegated_version.$action,
+	    "properties": {
+		"version": "0.2.1",
+	    },
+	});
+
+	expect( app_version.version	).to.equal( "0.2.1" );
+    });
+
+    it("should revoke a delegation", async function () {
+	this.timeout( 20_000 );
+
+	await appstore_csr.revoke_publishing_delegation({
+	    "id": delegation1.id,
+	});
+
+	const delegations		= await appstore_csr.get_publishing_delegations({
+	    "for_app": app1.$id,
+	});
+
+	expect( delegations		).to.have.length( 0 );
+
+	await expect_reject( async () => {
+	    await bobby_appstore_csr.create_app_version( createAppVersionInput({
+		"version": "0.3.0",
+		"for_app": app1.$id,
+		"bundle_hashes": EMPTY_BUNDLE_HASHES,
+	    }) );
+	}, "has no publishing delegation" );
+    });
+
+}
+
+
+function host_liveness_tests () {
+    const dna				= new DnaHash( crypto.randomBytes(32) );
+    const target			= {
+	dna,
+	"zome":				"apphub_csr",
+	"function":			"get_webapp_package_entry",
+    };
+    const online_host			= new AgentPubKey( crypto.randomBytes(32) );
+    const offline_host			= new AgentPubKey( crypto.randomBytes(32) );
+    const unknown_host			= new AgentPubKey( crypto.randomBytes(32) );
+
+    it("should record host responses in the cell", async function () {
+	const first			= await appstore_csr.record_host_response({
+	    ...target,
+	    "host":			offline_host,
+	    "responded":		false,
+	});
+	const liveness			= await appstore_csr.record_host_response({
+	    ...target,
+	    "host":			offline_host,
+	    "responded":		false,
+	});
+	await appstore_csr.record_host_response({
+	    ...target,
+	    "host":			online_host,
+	    "responded":		true,
+	});
+
+	log.normal("Host liveness: %s", json.debug(liveness) );
+
+	// The second response updates the same record
+	expect( String(new ActionHash( liveness.id )) ).to.equal( String(new ActionHash( first.id )) );
+	expect( liveness.content.failures	).to.equal( 2 );
+	expect( liveness.content.last_pong	).to.be.null;
+    });
+
+    it("should rank responsive, then unknown, then failing hosts", async function () {
+	const ranked			= await appstore_csr.get_ranked_hosts({
+	    ...target,
+	    "hosts":			[ offline_host, unknown_host, online_host ],
+	});
+
+	log.normal("Ranked hosts: %s", json.debug(ranked) );
+
+	expect(
+	    ranked.map( ranked_host => String(new AgentPubKey( ranked_host.host )) )
+	).to.deep.equal([
+	    String(online_host),
+	    String(unknown_host),
+	    String(offline_host),
+	]);
+    });
+
+    it("should not share host liveness with other agents", async function () {
+	const ranked			= await bobby_appstore_csr.get_ranked_hosts({
+	    ...target,
+	    "hosts":			null,
+	});
+
+	expect( ranked			).to.have.length( 0 );
+    });
+
+    it("should reset failures when a host responds", async function () {
+	const liveness			= await appstore_csr.record_host_response({
+	    ...target,
+	    "host":			offline_host,
+	    "responded":		true,
+	});
+
+	expect( liveness.content.failures	).to.equal( 0 );
+	expect( liveness.content.last_pong	).to.be.a("number");
+	expect( liveness.content.last_failure	).to.be.a("number");
+    });
+
 }
 
 
@@ -365,6 +555,24 @@ function errors_tests () {
 	}, `PublisherEntry icon cannot be larger than ${Math.floor(ICON_SIZE_LIMIT/1024)}KB (${ICON_SIZE_LIMIT} bytes)` );
     });
 
+    it("should return a structured error when icon is too big", async function () {
+	this.timeout( 10_000 );
+
+	let error;
+	try {
+	    const input			= createPublisherInput({
+		"icon": new Uint8Array( ICON_SIZE_LIMIT + 1 ).fill(0),
+	    });
+	    await appstore_csr.create_publisher( input );
+	} catch (err) {
+	    error			= parseAppStoreError( err );
+	}
+
+	expect( error.code		).to.equal( "ICON_TOO_LARGE" );
+	expect( error.entry_type	).to.equal( "PublisherEntry" );
+	expect( error.limit		).to.equal( ICON_SIZE_LIMIT );
+    });
+
     it("should fail to create app because icon is too big", async function () {
 	this.timeout( 10_000 );
 
@@ -377,6 +585,50 @@ function errors_tests () {
 	}, `AppEntry icon cannot be larger than ${Math.floor(ICON_SIZE_LIMIT/1024)}KB (${ICON_SIZE_LIMIT} bytes)` );
     });
 
+    it("should fail to create app because DevHub package is already claimed", async function () {
+	this.timeout( 10_000 );
+
+	await expect_reject( async () => {
+	    const input			= createAppInput({
+		"publisher": publisher1.$id,
+		"apphub_hrl": app1.apphub_hrl,
+		"apphub_hrl_hash": app1.apphub_hrl_hash,
+	    });
+	    await appstore_csr.create_app( input );
+	}, "is already claimed by App" );
+    });
+
+    it("should fail to create app because HRL target does not match hash", async function () {
+	this.timeout( 10_000 );
+
+	await expect_reject( async () => {
+	    const input			= createAppInput({
+		"publisher": publisher1.$id,
+		"apphub_hrl": {
+		    "dna": new DnaHash( crypto.randomBytes(32) ),
+		    "target": new EntryHash( crypto.randomBytes(32) ),
+		},
+	    });
+	    await appstore_csr.create_app( input );
+	}, "HRL target does not match 'apphub_hrl_hash'" );
+    });
+
+    it("should fail to create app because HRL action target cannot be resolved", async function () {
+	this.timeout( 10_000 );
+
+	// There are no DevHub hosts so the creating action of the target cannot be checked
+	await expect_reject( async () => {
+	    const input			= createAppInput({
+		"publisher": publisher1.$id,
+		"apphub_hrl": {
+		    "dna": new DnaHash( crypto.randomBytes(32) ),
+		    "target": new ActionHash( crypto.randomBytes(32) ),
+		},
+	    });
+	    await appstore_csr.create_app( input );
+	});
+    });
+
     it("should fail to update app because icon is too big", async function () {
 	this.timeout( 10_000 );
 
@@ -403,4 +655,71 @@ function errors_tests () {
 	    await bobby_appstore_csr.create_app_version( input );
 	}, "not in the editor list" );
     });
+
+    it("should fail to issue a delegation that does not expire in time", async function () {
+	const bobby_info		= await bobby_appstore_csr.whoami();
+
+	await expect_reject( async () => {
+	    await appstore_csr.issue_publishing_delegation({
+		"for_app": app1.$id,
+		"delegate": new AgentPubKey( bobby_info.agent_initial_pubkey ),
+		"expires_at": Date.now() + 365 * 24 * 60 * 60 * 1_000,
+	    });
+	}, "must expire after it is issued and within" );
+    });
+
+    it("should fail to publish under an expired delegation", async function () {
+	this.timeout( 20_000 );
+
+	const bobby_info		= await bobby_appstore_csr.whoami();
+	const delegation		= await appstore_csr.issue_publishing_delegation({
+	    "for_app": app1.$id,
+	    "delegate": new AgentPubKey( bobby_info.agent_initial_pubkey ),
+	    "expires_at": Date.now() + 2_000,
+	});
+
+	await new Promise( resolve => setTimeout( resolve, 3_000 ) );
+
+	await expect_reject( async () => {
+	    await bobby_appstore_csr.create_app_version( createAppVersionInput({
+		"version": "0.4.0",
+		"for_app": app1.$id,
+		"bundle_hashes": EMPTY_BUNDLE_HASHES,
+		"delegation": delegation.id,
+	    }) );
+	}, "does not cover author" );
+    });
+
+    it("should fail to publish under a delegation issued for another agent", async function () {
+	this.timeout( 20_000 );
+
+	const delegation		= await appstore_csr.issue_publishing_delegation({
+	    "for_app": app1.$id,
+	    "delegate": new AgentPubKey( crypto.randomBytes(32) ),
+	});
+
+	await expect_reject( async () => {
+	    await bobby_appstore_csr.create_app_version( createAppVersionInput({
+		"version": "0.4.0",
+		"for_app": app1.$id,
+		"bundle_hashes": EMPTY_BUNDLE_HASHES,
+		"delegation": delegation.id,
+	    }) );
+	}, "does not cover author" );
+    });
+
+    it("should fail to import catalog snapshot into the same DNA", async function () {
+	this.timeout( 20_000 );
+
+	const snapshot			= await appstore_csr.export_catalog_snapshot({});
+
+	let error;
+	try {
+	    await appstore_csr.import_catalog_snapshot( snapshot );
+	} catch (err) {
+	    error			= parseAppStoreError( err );
+	}
+
+	expect( error.code		).to.equal( "SNAPSHOT_FROM_THIS_DNA" );
+    });
 }
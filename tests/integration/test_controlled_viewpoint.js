@@ -100,7 +100,9 @@ describe("Controlled Viewpoint", () => {
     linearSuite("Publisher", publisher_tests.bind( this, holochain ) );
     linearSuite("App", app_tests.bind( this, holochain ) );
     linearSuite("Group Viewpoint", group_tests.bind( this, holochain ) );
+    linearSuite("Appeals", appeal_tests.bind( this, holochain ) );
     linearSuite("Errors", errors_tests.bind( this, holochain ) );
+    linearSuite("Viewpoint Paging", paging_tests.bind( this, holochain ) );
 
     after(async () => {
 	await holochain.destroy();
@@ -210,6 +212,25 @@ function group_tests () {
 	expect( ma_state.message	).to.equal( moderator_action.message );
     });
 
+    it("should get a page of apps from group view", async function () {
+	const page			= await appstore_csr.viewpoint_get_apps({
+	    "group_id": group1.$id,
+	    "limit": 10,
+	});
+
+	expect( page.items		).to.have.length( 0 );
+	expect( page.total		).to.equal( 0 );
+    });
+
+    it("should get removed app from group view", async function () {
+	const result			= await group1.$getApp( app1.$id );
+
+	log.debug( json.debug( result ) );
+
+	expect( result.entity.$id	).to.deep.equal( app1.$id );
+	expect( result.removed		).to.be.true;
+    });
+
     it("should unremove app from group view", async function () {
         this.timeout( 20_000 );
 
@@ -237,6 +258,170 @@ function group_tests () {
 	log.debug( json.debug( moderator_actions ) );
     });
 
+    it("should use saved viewpoint preference", async function () {
+        this.timeout( 30_000 );
+
+	// Fixture: the group hides app1 so the preference changes the result
+	await group1.$removeApp( app1.$id, "Hidden for viewpoint preference test" );
+
+	{
+	    const apps			= await appstore_csr.my_view_get_all_apps();
+	    expect( apps		).to.have.length( 1 );
+	}
+
+	await appstore_csr.set_my_viewpoint({
+	    "group_ids": [ group1.$id ],
+	});
+
+	const preference		= await appstore_csr.get_my_viewpoint();
+
+	expect( preference.content.group_ids	).to.have.length( 1 );
+
+	{
+	    const apps			= await appstore_csr.my_view_get_all_apps();
+	    expect( apps		).to.have.length( 0 );
+	}
+
+	// Clearing the preference falls back to all apps
+	await appstore_csr.set_my_viewpoint({
+	    "group_ids": [],
+	});
+
+	{
+	    const preference		= await appstore_csr.get_my_viewpoint();
+	    expect( preference.content.group_ids ).to.have.length( 0 );
+
+	    const apps			= await appstore_csr.my_view_get_all_apps();
+	    expect( apps		).to.have.length( 1 );
+	}
+
+	await group1.$unremoveApp( app1.$id, "Viewpoint preference test finished" );
+    });
+
+    it("should get moderation log", async function () {
+	const log_items			= await group1.$getModerationLog();
+
+	log.debug( json.debug( log_items ) );
+
+	expect( log_items		).to.have.length( 4 );
+	expect( log_items[0].action_type).to.equal( "Remove" );
+	expect( log_items[1].action_type).to.equal( "Restore" );
+	expect( log_items[2].action_type).to.equal( "Remove" );
+	expect( log_items[3].action_type).to.equal( "Restore" );
+	expect( log_items[0].subject_type).to.equal( "App" );
+    });
+
+    it("should export and verify moderation log", async function () {
+	const export_bytes		= await group1.$exportModerationLog();
+	const result			= await appstore_csr.verify_moderation_log_export( export_bytes );
+
+	log.debug( json.debug( result ) );
+
+	expect( result.verified		).to.be.true;
+    });
+
+}
+
+
+let appeal1;
+
+function appeal_tests () {
+
+    it("should appeal a removal", async function () {
+        this.timeout( 20_000 );
+
+	const moderator_action		= await group1.$removeApp(
+	    app1.$id,
+	    "App does not match its description"
+	);
+
+	appeal1				= await appstore_csr.create_appeal({
+	    "moderator_action": moderator_action.id,
+	    "message": "The description has been updated",
+	});
+
+	log.debug("Appeal: %s", json.debug( appeal1 ) );
+
+	const appeals			= await group1.$getOpenAppeals();
+
+	expect( appeals			).to.have.length( 1 );
+    });
+
+    it("should resolve appeal and restore app", async function () {
+        this.timeout( 20_000 );
+
+	const resolution		= await group1.$resolveAppeal(
+	    appeal1.id,
+	    "Description is now accurate",
+	    true,
+	);
+
+	log.debug("Appeal resolution: %s", json.debug( resolution ) );
+
+	expect( resolution.content.restore_action ).to.not.be.null;
+
+	{
+	    const appeals		= await group1.$getOpenAppeals();
+	    expect( appeals		).to.have.length( 0 );
+	}
+	{
+	    const apps			= await group1.$getAllApps();
+	    expect( apps		).to.have.length( 1 );
+	}
+    });
+
+    it("should appeal a publisher removal", async function () {
+        this.timeout( 30_000 );
+
+	const moderator_action		= await appstore_csr.update_publisher_moderated_state({
+	    "group_id": group1.$id,
+	    "publisher_id": publisher1.$id,
+	    "message": "Publisher profile impersonates another developer",
+	    "metadata": {
+		"remove": true,
+	    },
+	});
+
+	{
+	    const result		= await group1.$getPublisher( publisher1.$id );
+	    expect( result.removed	).to.be.true;
+	}
+	{
+	    const log_items		= await group1.$getModerationLog();
+	    const item			= log_items.find(
+		item => String(item.action) === String(moderator_action.action)
+	    );
+
+	    expect( item.subject_type	).to.equal( "Publisher" );
+	}
+
+	const appeal			= await appstore_csr.create_appeal({
+	    "moderator_action": moderator_action.id,
+	    "message": "The profile has been verified",
+	});
+
+	log.debug("Publisher appeal: %s", json.debug( appeal ) );
+
+	expect( String(appeal.content.subject_id) ).to.equal( String(publisher1.$id) );
+
+	const resolution		= await group1.$resolveAppeal(
+	    appeal.id,
+	    "Profile ownership confirmed",
+	    true,
+	);
+
+	expect( resolution.content.restore_action ).to.not.be.null;
+
+	{
+	    const result		= await group1.$getPublisher( publisher1.$id );
+	    expect( result.removed	).to.be.false;
+	}
+	{
+	    const appeals		= await group1.$getOpenAppeals();
+	    expect( appeals		).to.have.length( 0 );
+	}
+    });
+
 }
 
 
@@ -257,4 +442,91 @@ function errors_tests () {
 	}, "is not authorized to update content managed by group" );
     });
 
+    it("should fail to appeal because agent is not an app editor", async function () {
+	const moderator_action		= await group1.$getAppModeratedState( app1.$id );
+
+	await expect_reject( async () => {
+	    await bobby_appstore_csr.create_appeal({
+		"moderator_action": moderator_action.id,
+		"message": "malicious",
+	    });
+	}, "must be in the editors list" );
+    });
+
+}
+
+
+let group2;
+let paging_apps;
+
+function paging_tests () {
+
+    it("should page through apps that are not removed", async function () {
+        this.timeout( 60_000 );
+
+	group2				= await appstore_csr.create_group( createGroupInput(
+	    [ alice_client.agent_id ],
+	) );
+
+	paging_apps			= [];
+	for ( let i = 0; i < 3; i++ ) {
+	    paging_apps.push(
+		await appstore_csr.create_app( createAppInput({
+		    "publisher": publisher1.$id,
+		}) )
+	    );
+	}
+
+	await group2.$removeApp( paging_apps[1].$id, "Paging test removal" );
+	await group2.$removeApp( paging_apps[2].$id, "Paging test removal" );
+
+	const seen			= [];
+	let after			= null;
+	let pages			= 0;
+
+	do {
+	    const page			= await appstore_csr.viewpoint_get_apps({
+		"group_id": group2.$id,
+		"limit": 1,
+		after,
+	    });
+	    log.debug("Page %s: %s", pages, json.debug( page ) );
+
+	    expect( page.items.length	).to.be.at.most( 1 );
+	    expect( page.total		).to.equal( 2 );
+
+	    seen.push( ...page.items.map( app => String(app.$id) ) );
+	    after			= page.next;
+	    pages++;
+	} while ( after && pages < 10 );
+
+	expect( seen			).to.have.members([
+	    String(app1.$id),
+	    String(paging_apps[0].$id),
+	]);
+	expect( new Set( seen ).size	).to.equal( seen.length );
+    });
+
+    it("should get removed apps sorted by ID", async function () {
+	const removed			= await group2.$getAllRemovedApps();
+	const ids			= removed.map( app => new Uint8Array( app.$id ) );
+
+	expect( removed			).to.have.length( 2 );
+	expect(
+	    Buffer.compare( Buffer.from( ids[0] ), Buffer.from( ids[1] ) )
+	).to.be.below( 0 );
+    });
+
+    it("should backfill removed subject index", async function () {
+        this.timeout( 30_000 );
+
+	const count			= await appstore_csr.backfill_removed_subject_index( group2.$id );
+
+	expect( count			).to.equal( 2 );
+
+	const removed			= await group2.$getAllRemovedApps();
+
+	expect( removed			).to.have.length( 2 );
+    });
+
 }
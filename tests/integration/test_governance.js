@@ -0,0 +1,196 @@
+import { Logger }			from '@whi/weblogger';
+const log				= new Logger("test-governance", process.env.LOG_LEVEL );
+
+import path				from 'path';
+import crypto				from 'crypto';
+import { expect }			from 'chai';
+
+import json				from '@whi/json';
+import {
+    AgentPubKey,
+}					from '@spartan-hc/holo-hash';
+
+import { Holochain }			from '@spartan-hc/holochain-backdrop';
+
+import {
+    AppStoreCell,
+}					from '@holochain/appstore-zomelets';
+import {
+    AppInterfaceClient,
+}					from '@spartan-hc/app-interface-client';
+
+import {
+    expect_reject,
+    linearSuite,
+    createAppInput,
+    createPublisherInput,
+    createGroupInput,
+}					from '../utils.js';
+
+
+const __dirname				= path.dirname( new URL(import.meta.url).pathname );
+const APPSTORE_PATH			= path.join( __dirname, "../../happ/appstore.happ" );
+
+const network_seed			= crypto.randomBytes( 8 ).toString("hex");
+
+let app_port;
+let client;
+
+let governor_key;
+let governor_csr;
+let bobby_key;
+let bobby_csr;
+
+
+// Governance agents are DNA properties so the agent keys must exist before installing
+async function installAppStore ( admin, app_id, agent_key, properties ) {
+    await admin.installApp( app_id, agent_key, APPSTORE_PATH, {
+	network_seed,
+	"roles_settings": {
+	    "appstore": {
+		"type": "provisioned",
+		"value": {
+		    "modifiers": {
+			properties,
+		    },
+		},
+	    },
+	},
+    });
+    await admin.enableApp( app_id );
+
+    const { token }			= await admin.issueAppAuthenticationToken( app_id );
+    const app_client			= await client.app( token );
+    const appstore			= app_client.createCellInterface( "appstore", AppStoreCell );
+
+    return appstore.zomes.appstore_csr.functions;
+}
+
+
+describe("Governance", () => {
+    const holochain			= new Holochain({
+	"timeout": 60_000,
+	"default_stdout_loggers": log.level_rank > 3,
+    });
+
+    before(async function () {
+	this.timeout( 60_000 );
+
+	await holochain.start();
+
+	const admin			= holochain.admin;
+	app_port			= await holochain.ensureAppPort();
+
+	client				= new AppInterfaceClient( app_port, {
+	    "logging": process.env.LOG_LEVEL || "normal",
+	});
+
+	governor_key			= await admin.generateAgent();
+	bobby_key			= await admin.generateAgent();
+
+	const properties		= {
+	    "governance_agents": [ governor_key ],
+	};
+
+	governor_csr			= await installAppStore( admin, "governor", governor_key, properties );
+	bobby_csr			= await installAppStore( admin, "bobby", bobby_key, properties );
+
+	// Must call whoami on each cell to ensure that init has finished.
+	await governor_csr.whoami();
+	await bobby_csr.whoami();
+    });
+
+    linearSuite("Basic", basic_tests.bind( this, holochain ) );
+    linearSuite("Errors", errors_tests.bind( this, holochain ) );
+
+    after(async () => {
+	await holochain.destroy();
+    });
+
+});
+
+
+let governance_group;
+let bobby_group;
+let app1;
+
+function basic_tests () {
+
+    it("should get governance agents from DNA properties", async function () {
+	const agents			= await bobby_csr.get_governance_agents();
+
+	expect( agents			).to.have.length( 1 );
+	expect( String(agents[0])	).to.equal( String(new AgentPubKey( governor_key )) );
+
+	expect( await bobby_csr.get_governance_group() ).to.be.null;
+    });
+
+    it("should register governance group", async function () {
+	this.timeout( 20_000 );
+
+	governance_group		= await governor_csr.create_group(
+	    createGroupInput([ governor_key ])
+	);
+
+	await governor_csr.register_governance_group( governance_group.$id );
+
+	const group_id			= await bobby_csr.get_governance_group();
+
+	expect( String(group_id)	).to.equal( String(governance_group.$id) );
+    });
+
+    it("should hide app removed by governance group", async function () {
+	this.timeout( 30_000 );
+
+	const publisher			= await bobby_csr.create_publisher( createPublisherInput() );
+	app1				= await bobby_csr.create_app( createAppInput({
+	    "publisher": publisher.$id,
+	}) );
+
+	{
+	    const apps			= await bobby_csr.get_all_apps();
+	    expect( apps		).to.have.length( 1 );
+	}
+
+	await governance_group.$removeApp( app1.$id, "Legal takedown" );
+
+	{
+	    const apps			= await bobby_csr.get_all_apps();
+	    expect( apps		).to.have.length( 0 );
+	}
+	{
+	    const apps			= await bobby_csr.get_governance_removed_apps();
+	    expect( apps		).to.have.length( 1 );
+	}
+    });
+
+}
+
+
+function errors_tests () {
+
+    it("should fail to register governance group because agent is not a governance agent", async function () {
+	this.timeout( 20_000 );
+
+	bobby_group			= await bobby_csr.create_group(
+	    createGroupInput([ bobby_key ])
+	);
+
+	await expect_reject( async () => {
+	    await bobby_csr.register_governance_group( bobby_group.$id );
+	}, "is not one of the governance agents" );
+    });
+
+    it("should fail to register group with non-governance contributors", async function () {
+	this.timeout( 20_000 );
+
+	const group			= await governor_csr.create_group(
+	    createGroupInput([ governor_key ], bobby_key )
+	);
+
+	await expect_reject( async () => {
+	    await governor_csr.register_governance_group( group.$id );
+	}, "only governance agents as contributors" );
+    });
+
+}
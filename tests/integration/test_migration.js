@@ -0,0 +1,361 @@
+import { Logger }			from '@whi/weblogger';
+const log				= new Logger("test-migration", process.env.LOG_LEVEL );
+
+import path				from 'path';
+import { expect }			from 'chai';
+
+import json				from '@whi/json';
+import { decode }			from '@msgpack/msgpack';
+import {
+    DnaHash,
+    AgentPubKey,
+    ActionHash,
+}					from '@spartan-hc/holo-hash';
+
+import { Holochain }			from '@spartan-hc/holochain-backdrop';
+
+import {
+    parseAppStoreError,
+}					from '@holochain/appstore-zomelets';
+import {
+    AppInterfaceClient,
+}					from '@spartan-hc/app-interface-client';
+
+import {
+    EMPTY_BUNDLE_HASHES,
+    installAppStore,
+    linearSuite,
+    createAppInput,
+    createAppVersionInput,
+    createPublisherInput,
+}					from '../utils.js';
+
+
+const __dirname				= path.dirname( new URL(import.meta.url).pathname );
+const APPSTORE_PATH			= path.join( __dirname, "../../happ/appstore.happ" );
+
+let app_port;
+let client;
+let agent_key;
+
+let previous_csr;
+let next_csr;
+let seed_csr;
+let stranger_csr;
+
+let publisher1;
+
+
+describe("Migration", () => {
+    const holochain			= new Holochain({
+	"timeout": 60_000,
+	"default_stdout_loggers": log.level_rank > 3,
+    });
+
+    before(async function () {
+	this.timeout( 60_000 );
+
+	await holochain.start();
+
+	const admin			= holochain.admin;
+	app_port			= await holochain.ensureAppPort();
+
+	client				= new AppInterfaceClient( app_port, {
+	    "logging": process.env.LOG_LEVEL || "normal",
+	});
+
+	agent_key			= await admin.generateAgent();
+
+	// A different network seed gives the replacement DNA a different hash
+	previous_csr			= await installAppStore( client, admin, APPSTORE_PATH, "previous", agent_key );
+	next_csr			= await installAppStore( client, admin, APPSTORE_PATH, "next", agent_key );
+	seed_csr			= await installAppStore( client, admin, APPSTORE_PATH, "seed", agent_key );
+	stranger_csr			= await installAppStore(
+	    client, admin, APPSTORE_PATH, "stranger", await admin.generateAgent()
+	);
+    });
+
+    linearSuite("Schema", schema_tests.bind( this, holochain ) );
+    linearSuite("Chain", chain_tests.bind( this, holochain ) );
+    linearSuite("Catalog Snapshot", snapshot_tests.bind( this, holochain ) );
+
+    after(async () => {
+	await holochain.destroy();
+    });
+
+});
+
+
+function schema_tests () {
+
+    it("should import an old-layout Publisher at the current schema version", async function () {
+	this.timeout( 30_000 );
+
+	publisher1			= await previous_csr.create_publisher( createPublisherInput() );
+
+	const export_data		= await previous_csr.export_my_entries();
+
+	// Drop every field that was added after the first Publisher layout
+	for ( let entity of export_data.publishers ) {
+	    for ( let field of [
+		"metadata",
+		"description",
+		"email",
+		"deprecation",
+		"provenance",
+		"schema_version",
+	    ] )
+		delete entity.content[ field ];
+	}
+
+	log.normal("Old-layout export: %s", json.debug(export_data.publishers) );
+
+	const report			= await next_csr.import_my_entries( export_data );
+
+	expect( report.publishers	).to.have.length( 1 );
+
+	const publisher			= report.publishers[0].content;
+
+	expect( publisher.schema_version	).to.equal( 2 );
+	expect( publisher.metadata		).to.deep.equal( {} );
+	expect( String( new DnaHash( publisher.provenance.dna ) )
+					).to.equal( String( new DnaHash( export_data.dna ) ) );
+
+	const upgrades			= await next_csr.upgrade_my_entries();
+
+	expect( upgrades.publishers	).to.have.length( 0 );
+    });
+
+}
+
+
+const MANIFEST				= {
+    "name": "Chess",
+    "happ_name": "chess",
+    "happ_description": null,
+    "ui_hash": "",
+    "happ_hash": "",
+    "roles": [],
+};
+
+let previous_dna;
+let next_dna;
+let export_data;
+let close_action;
+let app1;
+let app2;
+let app_version1;
+
+function hashString ( bytes, type = ActionHash ) {
+    return String( new type( bytes ) );
+}
+
+function chain_tests () {
+
+    it("should export every entity and icon", async function () {
+	this.timeout( 30_000 );
+
+	app1				= await previous_csr.create_app( createAppInput({
+	    "publisher": publisher1.$id,
+	}) );
+	app2				= await previous_csr.create_app( createAppInput({
+	    "title": "Chess 2",
+	    "publisher": publisher1.$id,
+	}) );
+	app_version1			= await previous_csr.create_app_version( createAppVersionInput({
+	    "for_app": app1.$id,
+	    "bundle_hashes": EMPTY_BUNDLE_HASHES,
+	    "manifest": MANIFEST,
+	}) );
+
+	await previous_csr.deprecate_app({
+	    "base": app1.$action,
+	    "message": "Replaced by Chess 2",
+	    "recommended_alternatives": [ app2.$id ],
+	});
+
+	export_data			= await previous_csr.export_my_entries();
+	previous_dna			= new DnaHash( export_data.dna );
+
+	log.normal("Export: %s", json.debug(export_data) );
+
+	expect( export_data.publishers	).to.have.length( 1 );
+	expect( export_data.apps	).to.have.length( 2 );
+	expect( export_data.app_versions	).to.have.length( 1 );
+	// 1 publisher icon and 2 app icons
+	expect( export_data.memories	).to.have.length( 3 );
+	expect( export_data.app_versions[0].content.manifest ).to.deep.equal( MANIFEST );
+    });
+
+    it("should close the previous chain", async function () {
+	// An export of the (empty) next chain is the simplest way to learn its DNA hash
+	next_dna			= new DnaHash( ( await next_csr.export_my_entries() ).dna );
+
+	expect( String( next_dna )	).to.not.equal( String( previous_dna ) );
+
+	close_action			= new ActionHash( await previous_csr.close_my_chain( next_dna ) );
+    });
+
+    it("should open the next chain", async function () {
+	const open_action		= await next_csr.open_my_chain({
+	    previous_dna,
+	    close_action,
+	});
+
+	expect( open_action		).to.have.length( 39 );
+    });
+
+    it("should import with provenance, deprecations and manifests", async function () {
+	this.timeout( 30_000 );
+
+	const report			= await next_csr.import_my_entries( export_data );
+
+	expect( report.publishers	).to.have.length( 1 );
+	expect( report.apps		).to.have.length( 2 );
+	expect( report.app_versions	).to.have.length( 1 );
+
+	const [ new_app1, new_app2 ]	= report.apps;
+	const new_version		= report.app_versions[0];
+
+	for ( let [ entity, original ] of [
+	    [ new_app1,		app1 ],
+	    [ new_app2,		app2 ],
+	    [ new_version,	app_version1 ],
+	] ) {
+	    const provenance		= entity.content.provenance;
+
+	    expect( hashString( provenance.dna, DnaHash ) ).to.equal( String( previous_dna ) );
+	    expect( hashString( provenance.id )		).to.equal( String( original.$id ) );
+	}
+
+	// The recommended alternative points at the imported copy of App 2
+	const deprecation		= new_app1.content.deprecation;
+
+	expect( deprecation.message	).to.equal( "Replaced by Chess 2" );
+	expect( deprecation.recommended_alternatives.map( id => hashString( id ) )
+					).to.deep.equal([ hashString( new_app2.id ) ]);
+
+	expect( hashString( new_version.content.for_app ) ).to.equal( hashString( new_app1.id ) );
+	expect( new_version.content.manifest	).to.deep.equal( MANIFEST );
+	expect( new_version.content.published_at
+					).to.equal( app_version1.published_at );
+    });
+
+}
+
+
+let signed_pages			= [];
+let snapshot_pages			= [];
+
+function snapshot_tests () {
+
+    it("should export the catalog in signed pages", async function () {
+	this.timeout( 30_000 );
+
+	let more			= true;
+
+	for ( let page = 0; more; page++ ) {
+	    const signed		= await next_csr.export_catalog_snapshot({
+		page,
+		"page_size": 2,
+	    });
+	    const snapshot		= decode( decode( signed ).snapshot );
+
+	    log.normal("Snapshot page %s: %s", page, json.debug(snapshot) );
+
+	    expect( snapshot.page	).to.equal( page );
+
+	    signed_pages.push( signed );
+	    snapshot_pages.push( snapshot );
+	    more			= snapshot.more;
+	}
+
+	// 2 Publishers, 2 Apps and 1 App Version
+	expect( snapshot_pages		).to.have.length( 3 );
+	expect( snapshot_pages.map( page => page.publishers.length ) ).to.deep.equal([ 2, 0, 0 ]);
+	expect( snapshot_pages.map( page => page.apps.length ) ).to.deep.equal([ 0, 2, 0 ]);
+	expect( snapshot_pages.map( page => page.app_versions.length ) ).to.deep.equal([ 0, 0, 1 ]);
+    });
+
+    it("should reject a snapshot from an untrusted signer", async function () {
+	let error;
+	try {
+	    await stranger_csr.import_catalog_snapshot( signed_pages[0] );
+	} catch (err) {
+	    error			= parseAppStoreError( err );
+	}
+
+	expect( error.code		).to.equal( "UNTRUSTED_SNAPSHOT_SIGNER" );
+    });
+
+    it("should import every page with the original IDs, actions and authors", async function () {
+	this.timeout( 60_000 );
+
+	const imported			= {
+	    "publishers": [],
+	    "apps": [],
+	    "app_versions": [],
+	};
+
+	// Apps on the second page find their Publishers from the first page
+	for ( let signed of signed_pages ) {
+	    const report		= await seed_csr.import_catalog_snapshot( signed );
+
+	    for ( let key in imported )
+		imported[ key ].push( ...report[ key ] );
+	}
+
+	for ( let key in imported ) {
+	    const originals		= snapshot_pages.flatMap( page => page[ key ] );
+
+	    expect( imported[ key ]	).to.have.length( originals.length );
+
+	    for ( let entity of imported[ key ] ) {
+		const provenance	= entity.content.provenance;
+		const original		= originals.find(
+		    original => hashString( original.id ) === hashString( provenance.id )
+		);
+
+		expect( original	).to.not.be.undefined;
+		expect( hashString( provenance.dna, DnaHash ) ).to.equal( String( next_dna ) );
+		expect( hashString( provenance.action )	).to.equal( hashString( original.action ) );
+		expect( hashString( provenance.author, AgentPubKey )
+					).to.equal( hashString( original.content.author, AgentPubKey ) );
+	    }
+	}
+
+	// References point at the copies in this network
+	const seed_publisher_ids	= imported.publishers.map( entity => hashString( entity.id ) );
+	const seed_app_ids		= imported.apps.map( entity => hashString( entity.id ) );
+
+	for ( let app of imported.apps )
+	    expect( seed_publisher_ids	).to.include( hashString( app.content.publisher ) );
+
+	for ( let app_version of imported.app_versions )
+	    expect( seed_app_ids	).to.include( hashString( app_version.content.for_app ) );
+    });
+
+    it("should not duplicate entities when a page is imported again", async function () {
+	this.timeout( 60_000 );
+
+	for ( let signed of signed_pages ) {
+	    const report		= await seed_csr.import_catalog_snapshot( signed );
+
+	    expect( report.publishers	).to.have.length( 0 );
+	    expect( report.apps		).to.have.length( 0 );
+	    expect( report.app_versions	).to.have.length( 0 );
+	}
+
+	const publishers		= await seed_csr.get_my_publishers();
+	const apps			= await seed_csr.get_my_apps();
+	let app_versions		= [];
+
+	for ( let app of apps )
+	    app_versions.push( ...await app.$getVersions() );
+
+	expect( publishers		).to.have.length( 2 );
+	expect( apps			).to.have.length( 2 );
+	expect( app_versions		).to.have.length( 1 );
+    });
+
+}
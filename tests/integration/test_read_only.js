@@ -0,0 +1,145 @@
+import { Logger }			from '@whi/weblogger';
+const log				= new Logger("test-read-only", process.env.LOG_LEVEL );
+
+import path				from 'path';
+import crypto				from 'crypto';
+import { expect }			from 'chai';
+
+import json				from '@whi/json';
+
+import { Holochain }			from '@spartan-hc/holochain-backdrop';
+
+import {
+    AppStoreCell,
+}					from '@holochain/appstore-zomelets';
+import {
+    AppInterfaceClient,
+}					from '@spartan-hc/app-interface-client';
+
+import {
+    linearSuite,
+}					from '../utils.js';
+
+
+const __dirname				= path.dirname( new URL(import.meta.url).pathname );
+const APPSTORE_PATH			= path.join( __dirname, "../../happ/appstore.happ" );
+
+const network_seed			= crypto.randomBytes( 8 ).toString("hex");
+
+let app_port;
+let client;
+
+let default_csr;
+let configured_csr;
+
+
+// The read-only grant is created in init from the DNA properties
+async function installAppStore ( admin, app_id, properties ) {
+    const agent_key			= await admin.generateAgent();
+
+    await admin.installApp( app_id, agent_key, APPSTORE_PATH, {
+	network_seed,
+	"roles_settings": {
+	    "appstore": {
+		"type": "provisioned",
+		"value": {
+		    "modifiers": {
+			properties,
+		    },
+		},
+	    },
+	},
+    });
+    await admin.enableApp( app_id );
+
+    const { token }			= await admin.issueAppAuthenticationToken( app_id );
+    const app_client			= await client.app( token );
+    const appstore			= app_client.createCellInterface( "appstore", AppStoreCell );
+
+    return appstore.zomes.appstore_csr.functions;
+}
+
+
+describe("Read-only Access", () => {
+    const holochain			= new Holochain({
+	"timeout": 60_000,
+	"default_stdout_loggers": log.level_rank > 3,
+    });
+
+    before(async function () {
+	this.timeout( 60_000 );
+
+	await holochain.start();
+
+	const admin			= holochain.admin;
+	app_port			= await holochain.ensureAppPort();
+
+	client				= new AppInterfaceClient( app_port, {
+	    "logging": process.env.LOG_LEVEL || "normal",
+	});
+
+	default_csr			= await installAppStore( admin, "default", {} );
+	configured_csr			= await installAppStore( admin, "configured", {
+	    "read_only_functions": [
+		"get_app",
+		"get_all_apps",
+		"get_",
+		"get_apps",
+		"get_my_viewpoint",
+		"get_app_version_bundle",
+		"create_app",
+	    ],
+	});
+
+	// Must call whoami on each cell to ensure that init has finished.
+	await default_csr.whoami();
+	await configured_csr.whoami();
+    });
+
+    linearSuite("Grant", grant_tests.bind( this, holochain ) );
+
+    after(async () => {
+	await holochain.destroy();
+    });
+
+});
+
+
+function grant_tests () {
+
+    it("should grant only DHT reads by default", async function () {
+	const functions			= await default_csr.get_read_only_functions();
+
+	log.normal("Default read-only functions: %s", json.debug(functions) );
+
+	expect( functions		).to.include.members([
+	    "get_app",
+	    "get_all_apps",
+	    "viewpoint_get_apps",
+	    "hash_webapp_entry",
+	]);
+
+	for ( let name of [
+	    "get_my_viewpoint",
+	    "get_ranked_hosts",
+	    "get_app_version_bundle",
+	    "get_app_version_delta",
+	    "get_app_version_download_manifest",
+	    "create_app",
+	] ) {
+	    expect( functions		).to.not.include( name );
+	}
+    });
+
+    it("should grant only configured names that exactly match the allow-list", async function () {
+	const functions			= await configured_csr.get_read_only_functions();
+
+	log.normal("Configured read-only functions: %s", json.debug(functions) );
+
+	expect( functions		).to.deep.equal([
+	    "get_all_apps",
+	    "get_app",
+	]);
+    });
+
+}
@@ -0,0 +1,152 @@
+import { Logger }			from '@whi/weblogger';
+const log				= new Logger("test-signals", process.env.LOG_LEVEL );
+
+import path				from 'path';
+import { expect }			from 'chai';
+
+import json				from '@whi/json';
+import {
+    ActionHash,
+}					from '@spartan-hc/holo-hash';
+
+import { Holochain }			from '@spartan-hc/holochain-backdrop';
+
+import {
+    EMPTY_BUNDLE_HASHES,
+    setupAppStore,
+    linearSuite,
+    createAppInput,
+    createAppVersionInput,
+    createPublisherInput,
+}					from '../utils.js';
+
+
+const __dirname				= path.dirname( new URL(import.meta.url).pathname );
+const APPSTORE_DNA_PATH			= path.join( __dirname, "../../dnas/appstore.dna" );
+
+let appstore_csr;
+
+// Every signal payload emitted by the appstore_csr zome
+const signals				= [];
+
+
+describe("Signals", () => {
+    const holochain			= new Holochain({
+	"timeout": 60_000,
+	"default_stdout_loggers": log.level_rank > 3,
+    });
+
+    before(async function () {
+	this.timeout( 60_000 );
+
+	const { alice }			= await setupAppStore( holochain, APPSTORE_DNA_PATH, [
+	    "alice",
+	]);
+
+	alice.client.on("signal", ({ zome_name, payload }) => {
+	    if ( zome_name === "appstore_csr" )
+		signals.push( payload );
+	});
+
+	appstore_csr			= alice.appstore_csr;
+    });
+
+    linearSuite("Post Commit", post_commit_tests.bind( this, holochain ) );
+
+    after(async () => {
+	await holochain.destroy();
+    });
+
+});
+
+
+// Signals are emitted after the zome call returns so they may arrive slightly later
+async function nextSignal ( type, action ) {
+    for ( let attempt = 0; attempt < 50; attempt++ ) {
+	const signal			= signals.find(
+	    signal => signal.type === type
+		&& String( new ActionHash( signal.action ) ) === String( action )
+	);
+
+	if ( signal )
+	    return signal;
+
+	await new Promise( resolve => setTimeout( resolve, 100 ) );
+    }
+
+    throw new Error(`No ${type} signal for action ${action}`);
+}
+
+function expectSignal ( signal, change_type, id ) {
+    log.normal("Signal: %s", json.debug(signal) );
+
+    expect( signal.change_type		).to.equal( change_type );
+    expect( String( new ActionHash( signal.id ) ) ).to.equal( String( id ) );
+}
+
+let publisher1;
+let app1;
+let app_version1;
+
+function post_commit_tests () {
+
+    it("should signal a Publisher create", async function () {
+	publisher1			= await appstore_csr.create_publisher( createPublisherInput() );
+
+	const signal			= await nextSignal( "Publisher", publisher1.$action );
+
+	expectSignal( signal, "Create", publisher1.$id );
+	expect( signal.content.name	).to.equal( publisher1.name );
+    });
+
+    it("should signal a Publisher update", async function () {
+	await publisher1.$update({
+	    "name": "Holo Inc",
+	});
+
+	const signal			= await nextSignal( "Publisher", publisher1.$action );
+
+	expectSignal( signal, "Update", publisher1.$id );
+	expect( signal.content.name	).to.equal( "Holo Inc" );
+    });
+
+    it("should signal a Publisher deprecation", async function () {
+	await publisher1.$deprecate( "Oopsie!" );
+
+	const signal			= await nextSignal( "Publisher", publisher1.$action );
+
+	expectSignal( signal, "Deprecate", publisher1.$id );
+	expect( signal.content.deprecation.message ).to.equal( "Oopsie!" );
+    });
+
+    it("should signal an App create", async function () {
+	app1				= await appstore_csr.create_app( createAppInput({
+	    "publisher": publisher1.$id,
+	}) );
+
+	const signal			= await nextSignal( "App", app1.$action );
+
+	expectSignal( signal, "Create", app1.$id );
+    });
+
+    it("should signal an App Version create and delete", async function () {
+	app_version1			= await appstore_csr.create_app_version( createAppVersionInput({
+	    "for_app": app1.$id,
+	    "bundle_hashes": EMPTY_BUNDLE_HASHES,
+	}) );
+
+	expectSignal(
+	    await nextSignal( "AppVersion", app_version1.$action ),
+	    "Create", app_version1.$id,
+	);
+
+	const delete_action		= await appstore_csr.delete_app_version({
+	    "base": app_version1.$action,
+	});
+	const signal			= await nextSignal( "AppVersion", delete_action );
+
+	expectSignal( signal, "Delete", app_version1.$id );
+	expect( signal.content		).to.be.null;
+    });
+
+}
@@ -9,7 +9,19 @@ import { encode, decode }		from '@msgpack/msgpack';
 import {
     Bundle,
 }					from '@spartan-hc/bundles';
+
+import { Holochain }			from '@spartan-hc/holochain-backdrop';
+
+import {
+    AppStoreCell,
+    parseAppStoreError,
+}					from '@holochain/appstore-zomelets';
 import {
+    AppInterfaceClient,
+}					from '@spartan-hc/app-interface-client';
+
+import {
+    expect_reject,
     sha256,
 }					from '../utils.js';
 
@@ -19,6 +31,7 @@ let src_happ_bundle;
 
 const __dirname				= path.dirname( new URL(import.meta.url).pathname );
 const APPSTORE_PATH			= path.join( __dirname, "../../happ/appstore.happ" );
+const APPSTORE_DNA_PATH			= path.join( __dirname, "../../dnas/appstore.dna" );
 
 
 describe("Repacking error", () => {
@@ -97,3 +110,115 @@ describe("Repacking error", () => {
     });
 
 })
+
+
+describe("Bundle inspection", () => {
+    const holochain			= new Holochain({
+	"timeout": 60_000,
+	"default_stdout_loggers": log.level_rank > 3,
+    });
+
+    let appstore_csr;
+    let summary;
+
+    before(async function () {
+	this.timeout( 60_000 );
+
+	const installations		= await holochain.install([
+	    "alice",
+	], [
+	    {
+		"app_name": "test",
+		"bundle": {
+		    "appstore":	APPSTORE_DNA_PATH,
+		},
+	    },
+	]);
+
+	const app_port			= await holochain.ensureAppPort();
+	const client			= new AppInterfaceClient( app_port, {
+	    "logging": process.env.LOG_LEVEL || "normal",
+	});
+	const alice_client		= await client.app( installations.alice.test.auth.token );
+	const appstore			= alice_client.createCellInterface( "appstore", AppStoreCell );
+
+	appstore_csr			= appstore.zomes.appstore_csr.functions;
+
+	await appstore_csr.whoami();
+    });
+
+    it("should summarize webhapp manifest", async function () {
+	summary				= await appstore_csr.inspect_webhapp_bundle( src_bundle.toBytes() );
+
+	log.normal("Manifest summary: %s", JSON.stringify( summary, null, 4 ) );
+
+	const ui_bytes			= src_bundle.resources[ src_bundle.manifest.ui.bundled ];
+	const happ_bytes		= src_bundle.resources[ src_bundle.manifest.happ_manifest.bundled ];
+
+	expect( summary.name		).to.equal( "fake-webhapp-1" );
+	expect( summary.happ_name	).to.equal( src_happ_bundle.manifest.name );
+	expect( summary.ui_hash		).to.equal( sha256( ui_bytes ) );
+	expect( summary.happ_hash	).to.equal( sha256( happ_bytes ) );
+    });
+
+    it("should summarize roles with declared installed hashes", async function () {
+	const roles			= src_happ_bundle.manifest.roles;
+
+	expect( summary.roles		).to.have.length( roles.length );
+
+	roles.forEach( (role_manifest, i) => {
+	    const role			= summary.roles[i];
+	    const dna_bundle		= new Bundle(
+		src_happ_bundle.resources[ role_manifest.dna.bundled ]
+	    );
+
+	    expect( role.name		).to.equal( role_manifest.name );
+	    expect( role.dna_name	).to.equal( dna_bundle.manifest.name );
+	    expect( role.installed_hash	).to.equal( role_manifest.dna.installed_hash ?? null );
+	});
+    });
+
+    it("should summarize integrity zomes with the SHA-256 of their WASM", async function () {
+	src_happ_bundle.manifest.roles.forEach( (role_manifest, i) => {
+	    const role			= summary.roles[i];
+	    const dna_bundle		= new Bundle(
+		src_happ_bundle.resources[ role_manifest.dna.bundled ]
+	    );
+	    const zomes			= dna_bundle.manifest.integrity.zomes;
+
+	    expect( role.integrity_zomes ).to.have.length( zomes.length );
+
+	    zomes.forEach( (zome_manifest, j) => {
+		const zome		= role.integrity_zomes[j];
+
+		expect( zome.name	).to.equal( zome_manifest.name );
+		expect( zome.wasm_sha256 ).to.equal(
+		    sha256( dna_bundle.resources[ zome_manifest.bundled ] )
+		);
+	    });
+	});
+    });
+
+    it("should fail to inspect bytes that are not a bundle", async function () {
+	await expect_reject( async () => {
+	    await appstore_csr.inspect_webhapp_bundle( new Uint8Array( 100 ).fill( 1 ) );
+	}, "Failed to inspect webhapp bundle" );
+    });
+
+    it("should return a structured error for bytes that are not a bundle", async function () {
+	let error;
+	try {
+	    await appstore_csr.inspect_webhapp_bundle( new Uint8Array( 100 ).fill( 1 ) );
+	} catch (err) {
+	    error			= parseAppStoreError( err );
+	}
+
+	expect( error.code		).to.equal( "INVALID_WEBHAPP_BUNDLE" );
+	expect( error.reason		).to.be.a("string");
+    });
+
+    after(async () => {
+	await holochain.destroy();
+    });
+
+});
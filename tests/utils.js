@@ -7,6 +7,77 @@ import {
     ActionHash, EntryHash,
 }					from '@spartan-hc/holo-hash';
 
+import {
+    AppStoreCell,
+}					from '@holochain/appstore-zomelets';
+import {
+    AppInterfaceClient,
+}					from '@spartan-hc/app-interface-client';
+
+
+// Bundle hashes can be empty when the DevHub package is not verified
+export const EMPTY_BUNDLE_HASHES	= {
+    "hash": "",
+    "ui_hash": "",
+    "happ_hash": "",
+};
+
+
+// Install the App Store DNA for each agent and wait for init to finish
+//
+// Returns the app client and 'appstore_csr' functions of each agent (by name).
+export async function setupAppStore ( holochain, dna_path, agents ) {
+    const installations			= await holochain.install( agents, [
+	{
+	    "app_name": "test",
+	    "bundle": {
+		"appstore":	dna_path,
+	    },
+	},
+    ]);
+
+    const app_port			= await holochain.ensureAppPort();
+    const client			= new AppInterfaceClient( app_port, {
+	"logging": process.env.LOG_LEVEL || "normal",
+    });
+    const setups			= {};
+
+    for ( let agent of agents ) {
+	const app_client		= await client.app( installations[ agent ].test.auth.token );
+	const appstore			= app_client.createCellInterface( "appstore", AppStoreCell );
+	const appstore_csr		= appstore.zomes.appstore_csr.functions;
+
+	// Must call whoami on each cell to ensure that init has finished.
+	await appstore_csr.whoami();
+
+	setups[ agent ]			= {
+	    "client":			app_client,
+	    appstore_csr,
+	};
+    }
+
+    return setups;
+}
+
+
+// Install the App Store hApp as 'app_id' with a new network seed (so that it gets a different DNA
+// hash) and wait for init to finish
+export async function installAppStore ( client, admin, happ_path, app_id, agent ) {
+    await admin.installApp( app_id, agent, happ_path, {
+	"network_seed": crypto.randomBytes( 8 ).toString("hex"),
+    });
+    await admin.enableApp( app_id );
+
+    const { token }			= await admin.issueAppAuthenticationToken( app_id );
+    const app_client			= await client.app( token );
+    const appstore			= app_client.createCellInterface( "appstore", AppStoreCell );
+    const appstore_csr			= appstore.zomes.appstore_csr.functions;
+
+    await appstore_csr.whoami();
+
+    return appstore_csr;
+}
+
 
 export async function expect_reject ( cb, error, message ) {
     let failed				= false;
@@ -51,7 +122,10 @@ export function createPublisherInput ( overrides ) {
 };
 
 
+// Entry targets can be checked without a DevHub host (see 'validate_apphub_hrl')
 export function createAppInput ( overrides ) {
+    const apphub_hrl_hash		= new EntryHash( crypto.randomBytes(32) );
+
     return Object.assign({
 	"title": "Chess",
 	"subtitle": "The classic boardgame",
@@ -60,9 +134,9 @@ export function createAppInput ( overrides ) {
 	"publisher": new ActionHash( crypto.randomBytes(32) ),
 	"apphub_hrl": {
 	    "dna": new DnaHash( crypto.randomBytes(32) ),
-	    "target": new ActionHash( crypto.randomBytes(32) ),
+	    "target": apphub_hrl_hash,
 	},
-	"apphub_hrl_hash": new EntryHash( crypto.randomBytes(32) ),
+	"apphub_hrl_hash": apphub_hrl_hash,
 	"editors": [
 	    new AgentPubKey( crypto.randomBytes(32) )
 	],
@@ -71,14 +145,16 @@ export function createAppInput ( overrides ) {
 
 
 export function createAppVersionInput ( overrides ) {
+    const apphub_hrl_hash		= new EntryHash( crypto.randomBytes(32) );
+
     return Object.assign({
 	"version": "0.1.0",
 	"for_app": new ActionHash( crypto.randomBytes(32) ),
 	"apphub_hrl": {
 	    "dna": new DnaHash( crypto.randomBytes(32) ),
-	    "target": new ActionHash( crypto.randomBytes(32) ),
+	    "target": apphub_hrl_hash,
 	},
-	"apphub_hrl_hash": new EntryHash( crypto.randomBytes(32) ),
+	"apphub_hrl_hash": apphub_hrl_hash,
     }, overrides );
 };
 
@@ -104,6 +180,9 @@ export function sha256 ( bytes ) {
 
 
 export default {
+    EMPTY_BUNDLE_HASHES,
+    setupAppStore,
+    installAppStore,
     expect_reject,
     linearSuite,
     createAppInput,
@@ -10,7 +10,6 @@ use serde::de::{ Deserializer, Error };
 use lazy_static::lazy_static;
 use hdi::prelude::*;
 use hdi_extensions::{
-    guest_error,
     scoped_type_connector,
     ScopedTypeConnector,
 };
@@ -20,6 +19,9 @@ use hc_crud::{
 use mere_memory_types::{
     MemoryEntry,
 };
+use coop_content_sdk::{
+    GroupEntry,
+};
 
 
 lazy_static! {
@@ -29,6 +31,9 @@ lazy_static! {
     pub static ref ALL_APPS_ANCHOR : Path = Path::from(vec![
         Component::from( "apps".as_bytes().to_vec() ),
     ]);
+    pub static ref GOVERNANCE_ANCHOR : Path = Path::from(vec![
+        Component::from( "governance".as_bytes().to_vec() ),
+    ]);
 }
 
 
@@ -45,6 +50,18 @@ pub enum EntryTypes {
     ModeratorAction(ModeratorActionEntry),
     #[entry_type]
     GroupAnchor(GroupAnchorEntry),
+    #[entry_type]
+    Appeal(AppealEntry),
+    #[entry_type]
+    AppealResolution(AppealResolutionEntry),
+    #[entry_type(visibility = "private")]
+    ViewpointPreference(ViewpointPreferenceEntry),
+    #[entry_type(visibility = "private")]
+    HostLiveness(HostLivenessEntry),
+    #[entry_type]
+    PublishingDelegation(PublishingDelegationEntry),
+    #[entry_type]
+    BundleVerification(BundleVerificationEntry),
 }
 
 scoped_type_connector!(
@@ -67,6 +84,30 @@ scoped_type_connector!(
     EntryTypesUnit::GroupAnchor,
     EntryTypes::GroupAnchor( GroupAnchorEntry )
 );
+scoped_type_connector!(
+    EntryTypesUnit::Appeal,
+    EntryTypes::Appeal( AppealEntry )
+);
+scoped_type_connector!(
+    EntryTypesUnit::AppealResolution,
+    EntryTypes::AppealResolution( AppealResolutionEntry )
+);
+scoped_type_connector!(
+    EntryTypesUnit::ViewpointPreference,
+    EntryTypes::ViewpointPreference( ViewpointPreferenceEntry )
+);
+scoped_type_connector!(
+    EntryTypesUnit::HostLiveness,
+    EntryTypes::HostLiveness( HostLivenessEntry )
+);
+scoped_type_connector!(
+    EntryTypesUnit::PublishingDelegation,
+    EntryTypes::PublishingDelegation( PublishingDelegationEntry )
+);
+scoped_type_connector!(
+    EntryTypesUnit::BundleVerification,
+    EntryTypes::BundleVerification( BundleVerificationEntry )
+);
 
 // Entity implementations
 entry_model!( EntryTypes::Publisher( PublisherEntry ) );
@@ -74,6 +115,12 @@ entry_model!( EntryTypes::App( AppEntry ) );
 entry_model!( EntryTypes::AppVersion( AppVersionEntry ) );
 entry_model!( EntryTypes::ModeratorAction( ModeratorActionEntry ) );
 entry_model!( EntryTypes::GroupAnchor( GroupAnchorEntry ) );
+entry_model!( EntryTypes::Appeal( AppealEntry ) );
+entry_model!( EntryTypes::AppealResolution( AppealResolutionEntry ) );
+entry_model!( EntryTypes::ViewpointPreference( ViewpointPreferenceEntry ) );
+entry_model!( EntryTypes::HostLiveness( HostLivenessEntry ) );
+entry_model!( EntryTypes::PublishingDelegation( PublishingDelegationEntry ) );
+entry_model!( EntryTypes::BundleVerification( BundleVerificationEntry ) );
 
 
 #[hdk_link_types]
@@ -88,6 +135,26 @@ pub enum LinkTypes {
     AppToAppVersion,
 
     GroupAnchorToModeratorAction,
+
+    ModeratorActionToAppeal,
+    GroupAnchorToAppeal,
+    AppealToAppealResolution,
+
+    GroupAnchorToRemovedSubject,
+
+    ApphubHrlHashToApp,
+
+    DnaToAppVersion,
+
+    AppVersionToMirror,
+
+    AppToPublishingDelegation,
+
+    ChangeLogToAction,
+
+    GovernanceToGroup,
+
+    AppVersionToBundleVerification,
 }
 
 impl<'de> Deserialize<'de> for LinkTypes {
@@ -105,6 +172,26 @@ impl<'de> Deserialize<'de> for LinkTypes {
 
 	    "GroupAnchorToModeratorAction" => Ok(LinkTypes::GroupAnchorToModeratorAction),
 
+	    "ModeratorActionToAppeal" => Ok(LinkTypes::ModeratorActionToAppeal),
+	    "GroupAnchorToAppeal" => Ok(LinkTypes::GroupAnchorToAppeal),
+	    "AppealToAppealResolution" => Ok(LinkTypes::AppealToAppealResolution),
+
+	    "GroupAnchorToRemovedSubject" => Ok(LinkTypes::GroupAnchorToRemovedSubject),
+
+	    "ApphubHrlHashToApp" => Ok(LinkTypes::ApphubHrlHashToApp),
+
+	    "DnaToAppVersion" => Ok(LinkTypes::DnaToAppVersion),
+
+	    "AppVersionToMirror" => Ok(LinkTypes::AppVersionToMirror),
+
+	    "AppToPublishingDelegation" => Ok(LinkTypes::AppToPublishingDelegation),
+
+	    "ChangeLogToAction" => Ok(LinkTypes::ChangeLogToAction),
+
+	    "GovernanceToGroup" => Ok(LinkTypes::GovernanceToGroup),
+
+	    "AppVersionToBundleVerification" => Ok(LinkTypes::AppVersionToBundleVerification),
+
 	    value => Err(D::Error::custom(format!("No LinkTypes value matching '{}'", value ))),
 	}
     }
@@ -113,6 +200,179 @@ impl<'de> Deserialize<'de> for LinkTypes {
 
 const ICON_SIZE_LIMIT : u64 = 204_800;
 
+/// Width of the time buckets that [`LinkTypes::ChangeLogToAction`] links are grouped into
+pub const CHANGE_LOG_BUCKET_MS : u64 = 86_400_000;
+
+
+/// Get the base address used by [`LinkTypes::DnaToAppVersion`] links for a DNA
+pub fn dna_link_base(dna_hash: &DnaHash) -> ExternalHash {
+    ExternalHash::from_raw_36( dna_hash.get_raw_36().to_vec() )
+}
+
+
+/// Create the tag for a [`LinkTypes::GroupAnchorToRemovedSubject`] link
+///
+/// Format: `<subject type>::<moderator action hash (39 raw bytes)>`
+pub fn removed_subject_tag(subject_type: &str, moderator_action: &ActionHash) -> LinkTag {
+    let mut bytes = format!("{}::", subject_type ).into_bytes();
+    bytes.extend( moderator_action.get_raw_39() );
+
+    LinkTag::new( bytes )
+}
+
+
+/// Split a [`LinkTypes::GroupAnchorToRemovedSubject`] link tag into its subject type and
+/// moderator action hash
+pub fn parse_removed_subject_tag(tag: &LinkTag) -> ExternResult<(String, ActionHash)> {
+    let bytes = tag.as_ref();
+
+    if bytes.len() < 41 {
+        return Err( AppStoreError::InvalidRemovedSubjectTag {
+            reason: format!("too short ({} bytes)", bytes.len() ),
+        }.into() );
+    }
+
+    let (prefix, raw_hash) = bytes.split_at( bytes.len() - 39 );
+    let subject_type = match prefix.strip_suffix( b"::" ) {
+        Some(subject_type) => String::from_utf8( subject_type.to_vec() )
+            .map_err(|err| AppStoreError::InvalidRemovedSubjectTag {
+                reason: format!("invalid subject type: {}", err ),
+            })?,
+        None => return Err( AppStoreError::InvalidRemovedSubjectTag {
+            reason: "missing the '::' separator".to_string(),
+        }.into() ),
+    };
+    let moderator_action = ActionHash::try_from_raw_39( raw_hash.to_vec() )
+        .map_err(|err| AppStoreError::InvalidRemovedSubjectTag {
+            reason: format!("invalid moderator action hash: {:?}", err ),
+        })?;
+
+    Ok( (subject_type, moderator_action) )
+}
+
+
+/// Get the change-log anchor for the bucket containing the given time (in milliseconds)
+pub fn change_log_anchor(timestamp: u64) -> ExternResult<EntryHash> {
+    Path::from(vec![
+        Component::from( "change_log".as_bytes().to_vec() ),
+        Component::from( ( timestamp / CHANGE_LOG_BUCKET_MS ).to_string().as_bytes().to_vec() ),
+    ]).path_entry_hash()
+}
+
+
+/// Create the tag for a [`LinkTypes::ChangeLogToAction`] link
+pub fn change_log_tag(tag: ChangeLogTag) -> ExternResult<LinkTag> {
+    Ok( LinkTag::new( SerializedBytes::try_from( tag )?.bytes().to_owned() ) )
+}
+
+
+/// Decode the [`ChangeLogTag`] of a [`LinkTypes::ChangeLogToAction`] link
+pub fn parse_change_log_tag(tag: &LinkTag) -> ExternResult<ChangeLogTag> {
+    Ok( ChangeLogTag::try_from( SerializedBytes::from( UnsafeBytes::from( tag.as_ref().to_vec() ) ) )? )
+}
+
+
+/// Get the [`DnaProperties`] for this DNA (defaults when properties are not set)
+pub fn dna_properties() -> ExternResult<DnaProperties> {
+    let properties = dna_info()?.modifiers.properties;
+    let properties : Option<DnaProperties> = decode( properties.bytes() )
+        .map_err(|err| AppStoreError::InvalidDnaProperties {
+            reason: format!("{:?}", err ),
+        })?;
+
+    Ok( properties.unwrap_or_default() )
+}
+
+
+/// Get the read-only extern names configured in the DNA properties (non read-only names are
+/// dropped)
+pub fn read_only_functions() -> ExternResult<Option<Vec<String>>> {
+    Ok(
+        dna_properties()?.read_only_functions
+            .map( |names| names.into_iter()
+                   .filter( |name| is_read_only_function( name ) )
+                   .collect() )
+    )
+}
+
+
+/// Get the governance agents configured in the DNA properties (empty when unset)
+pub fn governance_agents() -> ExternResult<Vec<AgentPubKey>> {
+    Ok( dna_properties()?.governance_agents.unwrap_or_default() )
+}
+
+
+/// Check that a Group was founded by a governance agent with only governance agents as
+/// contributors
+pub fn is_governance_group(group_id: &ActionHash) -> ExternResult<bool> {
+    let governance_agents = governance_agents()?;
+
+    if governance_agents.is_empty() {
+        return Ok( false );
+    }
+
+    let record = must_get_valid_record( group_id.to_owned() )?;
+    let group_entry : GroupEntry = match record.clone().try_into() {
+        Ok(group_entry) => group_entry,
+        Err(_) => return Ok( false ),
+    };
+
+    Ok(
+        governance_agents.contains( record.action().author() )
+            && group_entry.contributors().iter()
+                .all( |agent| governance_agents.contains( agent ) )
+    )
+}
+
+
+/// Get the type and editors of a moderated subject (`None` when it is not an App or a Publisher)
+pub fn get_moderation_subject(
+    subject_id: &ActionHash,
+) -> ExternResult<Option<(ModerationSubjectType, Vec<AgentPubKey>)>> {
+    let record = must_get_valid_record( subject_id.to_owned() )?;
+
+    let entry_def = match record.action().entry_type() {
+        Some(EntryType::App(entry_def)) => entry_def.to_owned(),
+        _ => return Ok( None ),
+    };
+    let entry = match record.entry().as_option() {
+        Some(entry) => entry,
+        None => return Ok( None ),
+    };
+
+    Ok( match EntryTypes::deserialize_from_type(
+        entry_def.zome_index,
+        entry_def.entry_index,
+        entry,
+    )? {
+        Some(EntryTypes::App(app_entry)) => Some( (ModerationSubjectType::App, app_entry.editors) ),
+        Some(EntryTypes::Publisher(publisher_entry)) => Some( (ModerationSubjectType::Publisher, publisher_entry.editors) ),
+        _ => None,
+    })
+}
+
+
+pub fn validate_governance_auth(
+    entry: &ModeratorActionEntry,
+    author: &AgentPubKey,
+) -> ExternResult<()> {
+    if !is_governance_group( &entry.group_id.0 )? {
+        return Ok(());
+    }
+
+    let governance_agents = governance_agents()?;
+
+    // Contributors added to a governance group later cannot issue store-wide actions
+    if !governance_agents.contains( author ) {
+        return Err( AppStoreError::NotGovernanceAgent {
+            agent: author.to_owned(),
+            governance_agents,
+        }.into() );
+    }
+
+    Ok(())
+}
+
 
 pub fn validate_common_fields_create<'a,T,C>(
     action: &C, entry: &'a T
@@ -124,10 +384,193 @@ where
     let creation : EntryCreationAction = action.to_owned().into();
 
     if entry.author() != creation.author() {
-        return Err(guest_error!(format!(
-            "Entry author does not match Action author: {} != {}",
-            entry.author(), creation.author()
-        )));
+        return Err( AppStoreError::AuthorMismatch {
+            entry_author: entry.author().clone(),
+            action_author: creation.author().clone(),
+        }.into() );
+    }
+
+    Ok(())
+}
+
+
+/// Check that the author is an editor of the version's App or is covered by the version's
+/// publishing delegation at the action's timestamp
+///
+/// The delegation's window starts at the timestamp of the action that issued it.
+pub fn validate_app_version_publisher(
+    entry: &AppVersionEntry,
+    author: &AgentPubKey,
+    timestamp: &Timestamp,
+) -> ExternResult<()> {
+    let app_entry : AppEntry = must_get_valid_record( entry.for_app.clone() )?.try_into()?;
+
+    if app_entry.editors.contains( author ) {
+        return Ok(());
+    }
+
+    let delegation_id = match &entry.delegation {
+        Some(id) => id.clone(),
+        None => return Err( AppStoreError::MissingDelegation {
+            author: author.clone(),
+            editors: app_entry.editors.clone(),
+        }.into() ),
+    };
+    let delegation_record = must_get_valid_record( delegation_id.clone() )?;
+    let issued_at = delegation_record.action().timestamp().as_micros() as u64 / 1_000;
+    let delegation : PublishingDelegationEntry = delegation_record.try_into()?;
+    let at = timestamp.as_micros() as u64 / 1_000;
+
+    if !delegation.covers( &entry.for_app, author, issued_at, at ) {
+        return Err( AppStoreError::DelegationNotApplicable {
+            delegation: delegation_id.clone(),
+            author: author.clone(),
+            app: entry.for_app.clone(),
+            at,
+        }.into() );
+    }
+
+    Ok(())
+}
+
+
+/// Check an entry's schema version
+///
+/// New entries must use the current layout.  Updates may keep an older layout (so that they can
+/// be upgraded later) but cannot use an unknown version or go backwards.
+pub fn validate_schema_version<T>(
+    entry: &T,
+    previous: Option<&T>,
+) -> ExternResult<()>
+where
+    T: SchemaVersioned,
+{
+    if entry.schema_version() > T::SCHEMA_VERSION {
+        return Err( AppStoreError::UnknownSchemaVersion {
+            version: entry.schema_version(),
+            current: T::SCHEMA_VERSION,
+        }.into() );
+    }
+
+    match previous {
+        Some(previous) => {
+            if entry.schema_version() < previous.schema_version() {
+                return Err( AppStoreError::SchemaDowngrade {
+                    previous: previous.schema_version(),
+                    next: entry.schema_version(),
+                }.into() );
+            }
+        },
+        None => {
+            if entry.schema_version() != T::SCHEMA_VERSION {
+                return Err( AppStoreError::OutdatedSchemaVersion {
+                    version: entry.schema_version(),
+                    current: T::SCHEMA_VERSION,
+                }.into() );
+            }
+        },
+    }
+
+    Ok(())
+}
+
+
+/// Check that an imported entity's [`Provenance`] points at a previous DNA
+pub fn validate_provenance(
+    provenance: &Option<Provenance>,
+) -> ExternResult<()> {
+    if let Some(provenance) = provenance {
+        let dna = dna_info()?.hash;
+
+        if provenance.dna == dna {
+            return Err( AppStoreError::InvalidProvenance {
+                dna,
+            }.into() );
+        }
+    }
+
+    Ok(())
+}
+
+
+/// Check an App or App Version's DevHub HRL
+///
+/// An entry target must be `hrl_hash` itself.  An action target lives in the DevHub DNA, which
+/// validation cannot read, so the coordinator resolves it through the Portal before publishing.
+pub fn validate_apphub_hrl(
+    hrl: &HRL,
+    hrl_hash: &EntryHash,
+) -> ExternResult<()> {
+    // An entry target must be the same address as the expected entry hash
+    if let Some(target) = hrl.target.clone().into_entry_hash() {
+        if target != *hrl_hash {
+            return Err( AppStoreError::HrlTargetMismatch {
+                target: target.clone(),
+                apphub_hrl_hash: hrl_hash.clone(),
+            }.into() );
+        }
+    }
+
+    if let Some(allowed) = dna_properties()?.apphub_dnas {
+        if !allowed.contains( &hrl.dna ) {
+            return Err( AppStoreError::HrlDnaNotAllowed {
+                dna: hrl.dna.clone(),
+                allowed: allowed.clone(),
+            }.into() );
+        }
+    }
+
+    Ok(())
+}
+
+
+/// Check that a source ownership proof was signed by the maintainer of the listed package
+pub fn validate_source_ownership(
+    entry: &AppEntry,
+) -> ExternResult<()> {
+    if let Some(proof) = &entry.source_ownership {
+        let package_hash = proof.package_hash()?;
+
+        if package_hash != entry.apphub_hrl_hash {
+            return Err( AppStoreError::SourcePackageMismatch {
+                package_hash,
+                apphub_hrl_hash: entry.apphub_hrl_hash.clone(),
+            }.into() );
+        }
+
+        let maintainer = proof.package_maintainer();
+
+        if maintainer.as_ref() != Some( &proof.signer ) {
+            return Err( AppStoreError::NotPackageMaintainer {
+                signer: proof.signer.clone(),
+                maintainer,
+            }.into() );
+        }
+
+        if !verify_signature( proof.signer.clone(), proof.signature.clone(), entry.source_ownership_claim() )? {
+            return Err( AppStoreError::InvalidSourceOwnershipSignature {
+                signer: proof.signer.clone(),
+            }.into() );
+        }
+    }
+
+    Ok(())
+}
+
+
+pub fn validate_manifest_summary(
+    entry: &AppVersionEntry,
+) -> ExternResult<()> {
+    if let Some(manifest) = &entry.manifest {
+        if manifest.ui_hash != entry.bundle_hashes.ui_hash
+            || manifest.happ_hash != entry.bundle_hashes.happ_hash {
+            return Err( AppStoreError::ManifestSummaryMismatch {
+                ui_hash: manifest.ui_hash.clone(),
+                happ_hash: manifest.happ_hash.clone(),
+                bundle_ui_hash: entry.bundle_hashes.ui_hash.clone(),
+                bundle_happ_hash: entry.bundle_hashes.happ_hash.clone(),
+            }.into() );
+        }
     }
 
     Ok(())
@@ -143,10 +586,10 @@ pub fn validate_icon_field(
         .unwrap_or( memory.memory_size );
 
     if icon_size > ICON_SIZE_LIMIT {
-	return Err(guest_error!(format!(
-            "{} icon cannot be larger than {}KB ({} bytes)",
-            entry_type_name, ICON_SIZE_LIMIT/1024, ICON_SIZE_LIMIT
-        )));
+	return Err( AppStoreError::IconTooLarge {
+	    entry_type: entry_type_name.to_string(),
+	    limit: ICON_SIZE_LIMIT,
+	}.into() );
     }
 
     Ok(())
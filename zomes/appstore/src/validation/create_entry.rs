@@ -1,11 +1,26 @@
 use crate::{
     hdi,
     hdi_extensions,
+    AppStoreError,
+    AuthorRole,
 
     validate_common_fields_create,
     validate_icon_field,
+    validate_governance_auth,
+    validate_manifest_summary,
+    validate_apphub_hrl,
+    validate_source_ownership,
+    validate_app_version_publisher,
+    validate_schema_version,
+    validate_provenance,
+    get_moderation_subject,
 
     EntryTypes,
+    AppEntry,
+    AppVersionEntry,
+    ModeratorActionEntry,
+    AppealEntry,
+    MAX_DELEGATION_LIFETIME,
 
     coop_content_sdk::{
         validate_group_auth,
@@ -28,12 +43,19 @@ pub fn validation(
             // Check author field matches action author
             validate_common_fields_create( &create, &entry )?;
 
+            // Check that new entries use the current schema version
+            validate_schema_version( &entry, None )?;
+
+            // Check that a provenance points at a previous DNA
+            validate_provenance( &entry.provenance )?;
+
             // Check that the author field is in the editors list
             if !entry.editors.contains( &entry.author ) {
-                invalid!(format!(
-                    "Entry author ({}) must be in the editors list: {:?}",
-                    entry.author, entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Entry,
+                    agent: entry.author.clone(),
+                    editors: entry.editors.clone(),
+                }.to_message() )
             }
 
             // Check icon size
@@ -47,38 +69,69 @@ pub fn validation(
             // Check author field matches action author
             validate_common_fields_create( &create, &entry )?;
 
+            // Check that new entries use the current schema version
+            validate_schema_version( &entry, None )?;
+
+            // Check that a provenance points at a previous DNA
+            validate_provenance( &entry.provenance )?;
+
             // Check that the author field is in the editors list
             if !entry.editors.contains( &entry.author ) {
-                invalid!(format!(
-                    "Entry author ({}) must be in the editors list: {:?}",
-                    entry.author, entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Entry,
+                    agent: entry.author.clone(),
+                    editors: entry.editors.clone(),
+                }.to_message() )
             }
 
             // Check icon size
             validate_icon_field( &entry.icon, "AppEntry" )?;
 
+            // Check that the HRL agrees with its hash and points at an allowed DevHub
+            validate_apphub_hrl( &entry.apphub_hrl, &entry.apphub_hrl_hash )?;
+
+            // Check that an ownership proof was signed for this listing
+            validate_source_ownership( &entry )?;
+
             valid!()
         },
         EntryTypes::AppVersion(entry) => {
             // Check author field matches action author
             validate_common_fields_create( &create, &entry )?;
 
+            // Check that new entries use the current schema version
+            validate_schema_version( &entry, None )?;
+
+            // Check that a provenance points at a previous DNA
+            validate_provenance( &entry.provenance )?;
+
+            // Check that the author is an App editor or a delegated publisher
+            validate_app_version_publisher( &entry, &create.author, &create.timestamp )?;
+
+            // Check that a manifest summary agrees with the bundle hashes
+            validate_manifest_summary( &entry )?;
+
+            // Check that the HRL agrees with its hash and points at an allowed DevHub
+            validate_apphub_hrl( &entry.apphub_hrl, &entry.apphub_hrl_hash )?;
+
             valid!()
         },
         EntryTypes::ModeratorAction(entry) => {
             // Check author field matches action author
             if entry.author != create.author {
-                invalid!(format!(
-                    "Entry author does not match Action author: {} != {}",
-                    entry.author, create.author
-                ))
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: create.author.clone(),
+                }.to_message() )
             }
 
             // Check that the author is a contributor to the claimed group
-            validate_group_auth( &entry, create )
+            validate_group_auth( &entry, create.clone() )
                 .map_err(|err| guest_error!(err) )?;
 
+            // Check that governance actions are issued by the governance group's contributors
+            validate_governance_auth( &entry, &create.author )?;
+
             valid!()
         },
         EntryTypes::GroupAnchor(entry) => {
@@ -88,6 +141,181 @@ pub fn validation(
 
             valid!()
         },
+        EntryTypes::Appeal(entry) => {
+            // Check author field matches action author
+            if entry.author != create.author {
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: create.author.clone(),
+                }.to_message() )
+            }
+
+            let moderator_action_entry : ModeratorActionEntry = must_get_valid_record(
+                entry.moderator_action.clone()
+            )?.try_into()?;
+
+            // Check that the appeal matches the disputed moderator action
+            if moderator_action_entry.subject_id != entry.subject_id {
+                invalid!( AppStoreError::SubjectMismatch {
+                    context: "Appeal".to_string(),
+                    subject: entry.subject_id.clone(),
+                    expected: moderator_action_entry.subject_id.clone(),
+                }.to_message() )
+            }
+
+            if moderator_action_entry.group_id.0 != entry.group_id {
+                invalid!( AppStoreError::GroupMismatch {
+                    context: "Appeal".to_string(),
+                    group: entry.group_id.clone(),
+                    expected: moderator_action_entry.group_id.0.clone(),
+                }.to_message() )
+            }
+
+            // The moderated subject must be an App or a Publisher
+            let (_, editors) = match get_moderation_subject( &entry.subject_id )? {
+                Some(subject) => subject,
+                None => invalid!( AppStoreError::InvalidModerationSubject {
+                    subject: entry.subject_id.clone(),
+                }.to_message() ),
+            };
+
+            // Check that the author is one of the subject's editors
+            if !editors.contains( &entry.author ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Entry,
+                    agent: entry.author.clone(),
+                    editors,
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        EntryTypes::AppealResolution(entry) => {
+            // Check author field matches action author
+            if entry.author != create.author {
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: create.author.clone(),
+                }.to_message() )
+            }
+
+            let appeal_entry : AppealEntry = must_get_valid_record(
+                entry.appeal.clone()
+            )?.try_into()?;
+
+            // Check that the resolution belongs to the appeal's group
+            if appeal_entry.group_id != entry.group_id.0 {
+                invalid!( AppStoreError::GroupMismatch {
+                    context: "Appeal Resolution".to_string(),
+                    group: entry.group_id.0.clone(),
+                    expected: appeal_entry.group_id.clone(),
+                }.to_message() )
+            }
+
+            // Check that a recorded restore action is for the appealed subject
+            if let Some(restore_action) = entry.restore_action.clone() {
+                let moderator_action_entry : ModeratorActionEntry = must_get_valid_record(
+                    restore_action
+                )?.try_into()?;
+
+                if moderator_action_entry.subject_id != appeal_entry.subject_id {
+                    invalid!( AppStoreError::SubjectMismatch {
+                        context: "Restore action".to_string(),
+                        subject: moderator_action_entry.subject_id.clone(),
+                        expected: appeal_entry.subject_id.clone(),
+                    }.to_message() )
+                }
+
+                if moderator_action_entry.group_id.0 != appeal_entry.group_id {
+                    invalid!( AppStoreError::GroupMismatch {
+                        context: "Restore action".to_string(),
+                        group: moderator_action_entry.group_id.0.clone(),
+                        expected: appeal_entry.group_id.clone(),
+                    }.to_message() )
+                }
+            }
+
+            // Check that the author is a contributor to the claimed group
+            validate_group_auth( &entry, create )
+                .map_err(|err| guest_error!(err) )?;
+
+            valid!()
+        },
+        EntryTypes::ViewpointPreference(entry) => {
+            // Check author field matches action author
+            if entry.author != create.author {
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: create.author.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        EntryTypes::HostLiveness(entry) => {
+            // Check author field matches action author
+            if entry.author != create.author {
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: create.author.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        EntryTypes::PublishingDelegation(entry) => {
+            // Check author field matches action author
+            validate_common_fields_create( &create, &entry )?;
+
+            let app_entry : AppEntry = must_get_valid_record(
+                entry.for_app.clone()
+            )?.try_into()?;
+
+            // Check that the issuer is in the App's editors list
+            if !app_entry.editors.contains( &create.author ) {
+                invalid!(format!(
+                    "Delegation author ({}) must be in the App's editors list: {:?}",
+                    create.author, app_entry.editors,
+                ))
+            }
+
+            // Check that the delegation expires within the allowed lifetime of this action
+            let issued_at = create.timestamp.as_micros() as u64 / 1_000;
+
+            if entry.expires_at <= issued_at
+                || entry.expires_at - issued_at > MAX_DELEGATION_LIFETIME {
+                invalid!( AppStoreError::InvalidDelegationExpiry {
+                    issued_at,
+                    expires_at: entry.expires_at,
+                    max_lifetime: MAX_DELEGATION_LIFETIME,
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        EntryTypes::BundleVerification(entry) => {
+            // Check author field matches action author
+            if entry.author != create.author {
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: create.author.clone(),
+                }.to_message() )
+            }
+
+            let app_version_entry : AppVersionEntry = must_get_valid_record(
+                entry.app_version.clone()
+            )?.try_into()?;
+
+            // Check that the attestation is for the App Version's hashes
+            if app_version_entry.bundle_hashes != entry.bundle_hashes {
+                invalid!( AppStoreError::BundleHashMismatch {
+                    expected: format!("{:?}", app_version_entry.bundle_hashes ),
+                    received: format!("{:?}", entry.bundle_hashes ),
+                }.to_message() )
+            }
+
+            valid!()
+        },
         // _ => invalid!(format!("Create validation not implemented for entry type: {:#?}", create.entry_type )),
     }
 }
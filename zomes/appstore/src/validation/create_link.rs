@@ -1,22 +1,47 @@
 use crate::{
     hdi,
     hdi_extensions,
+    AppStoreError,
+    AuthorRole,
     LinkTypes,
+    ModeratorActionType,
+    parse_removed_subject_tag,
+    dna_link_base,
+    validate_app_version_publisher,
+    change_log_anchor,
+    parse_change_log_tag,
+    governance_agents,
+    is_governance_group,
+    ChangeSubjectType,
+    ChangeType,
 
     ALL_PUBLISHERS_ANCHOR,
     ALL_APPS_ANCHOR,
+    GOVERNANCE_ANCHOR,
 
     PublisherEntry,
     AppEntry,
     AppVersionEntry,
     ModeratorActionEntry,
     GroupAnchorEntry,
+    AppealEntry,
+    AppealResolutionEntry,
+    PublishingDelegationEntry,
+    BundleVerificationEntry,
+
+    coop_content_sdk::{
+        GroupEntry,
+    },
+};
+use mere_memory_types::{
+    MemoryEntry,
 };
 
 use hdi::prelude::*;
 use hdi_extensions::{
     AnyLinkableHashTransformer,
     verify_app_entry_struct,
+    trace_origin_root,
     // Macros
     guest_error,
     valid, invalid,
@@ -27,7 +52,7 @@ pub fn validation(
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     link_type: LinkTypes,
-    _tag: LinkTag,
+    tag: LinkTag,
     create: CreateLink,
 ) -> ExternResult<ValidateCallbackResult> {
     match link_type {
@@ -96,10 +121,11 @@ pub fn validation(
 
             // Author must be in publisher editors
             if !publisher_entry.editors.contains( &create.author ) {
-                invalid!(format!(
-                    "Link author ({}) is not in editor list: {:?}",
-                    create.author, publisher_entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Link,
+                    agent: create.author.clone(),
+                    editors: publisher_entry.editors.clone(),
+                }.to_message() )
             }
 
             verify_app_entry_struct::<AppEntry>( &target_address )?;
@@ -125,17 +151,26 @@ pub fn validation(
                 app_id.clone()
             )?.try_into()?;
 
-            // Link author must be in app editors
-            if !app_entry.editors.contains( &create.author ) {
-                invalid!(format!(
-                    "Author ({}) cannot link app -> app version because they are not in the editor list ({})",
-                    create.author, base_address,
-                ))
-            }
-
-            let app_version_entry : AppVersionEntry = must_get_valid_record(
+            let app_version_record = must_get_valid_record(
                 target_address.must_be_action_hash()?
-            )?.try_into()?;
+            )?;
+            // The delegation must cover the version's own action (not this link, which could be
+            // created after the delegation expires)
+            let version_timestamp = app_version_record.action().timestamp();
+            let app_version_entry : AppVersionEntry = app_version_record.try_into()?;
+
+            // Link author must be in app editors (or be the version's delegated publisher)
+            if !app_entry.editors.contains( &create.author )
+                && !( app_version_entry.author == create.author
+                       && validate_app_version_publisher(
+                           &app_version_entry, &create.author, &version_timestamp
+                       ).is_ok() ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Link,
+                    agent: create.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
+            }
 
             // Version must belong to app base address
             if app_version_entry.for_app != app_id {
@@ -168,6 +203,376 @@ pub fn validation(
 
             valid!()
         },
+        LinkTypes::ModeratorActionToAppeal => {
+            let moderator_action_id = base_address.must_be_action_hash()?;
+            let appeal_entry : AppealEntry = must_get_valid_record(
+                target_address.must_be_action_hash()?
+            )?.try_into()?;
+
+            // Check that the appeal disputes the Moderator Action base address
+            if appeal_entry.moderator_action != moderator_action_id {
+                invalid!(format!(
+                    "Appeal does not dispute Moderator Action base address: {} != {}",
+                    appeal_entry.moderator_action, moderator_action_id,
+                ))
+            }
+
+            // Link author must be the appeal author
+            if appeal_entry.author != create.author {
+                invalid!(format!(
+                    "Link author ({}) is not the Appeal author ({})",
+                    create.author, appeal_entry.author,
+                ))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupAnchorToAppeal => {
+            let group_anchor_entry : GroupAnchorEntry = must_get_entry(
+                base_address.must_be_entry_hash()?
+            )?.try_into()?;
+            let appeal_entry : AppealEntry = must_get_valid_record(
+                target_address.must_be_action_hash()?
+            )?.try_into()?;
+
+            // Check that the Appeal group matches the Group Anchor
+            if group_anchor_entry.group_id != appeal_entry.group_id {
+                invalid!(format!(
+                    "Appeal does not belong to Group Anchor: {} != {}",
+                    group_anchor_entry.group_id, appeal_entry.group_id,
+                ))
+            }
+
+            // Link author must be the appeal author
+            if appeal_entry.author != create.author {
+                invalid!(format!(
+                    "Link author ({}) is not the Appeal author ({})",
+                    create.author, appeal_entry.author,
+                ))
+            }
+
+            valid!()
+        },
+        LinkTypes::AppealToAppealResolution => {
+            let appeal_id = base_address.must_be_action_hash()?;
+            let appeal_resolution_entry : AppealResolutionEntry = must_get_valid_record(
+                target_address.must_be_action_hash()?
+            )?.try_into()?;
+
+            // Check that the resolution is for the Appeal base address
+            if appeal_resolution_entry.appeal != appeal_id {
+                invalid!(format!(
+                    "Appeal Resolution does not belong to Appeal base address: {} != {}",
+                    appeal_resolution_entry.appeal, appeal_id,
+                ))
+            }
+
+            // Link author must be the resolution author
+            if appeal_resolution_entry.author != create.author {
+                invalid!(format!(
+                    "Link author ({}) is not the Appeal Resolution author ({})",
+                    create.author, appeal_resolution_entry.author,
+                ))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupAnchorToRemovedSubject => {
+            let group_anchor_entry : GroupAnchorEntry = must_get_entry(
+                base_address.must_be_entry_hash()?
+            )?.try_into()?;
+            let subject_id = target_address.must_be_action_hash()?;
+            let (_, moderator_action_id) = parse_removed_subject_tag( &tag )?;
+            let moderator_action_entry : ModeratorActionEntry = must_get_valid_record(
+                moderator_action_id.clone()
+            )?.try_into()?;
+
+            // Check that the Moderator Action group matches the Group Anchor
+            if group_anchor_entry.group_id != moderator_action_entry.group_id.0 {
+                invalid!( AppStoreError::GroupMismatch {
+                    context: "Moderator Action".to_string(),
+                    group: moderator_action_entry.group_id.0.clone(),
+                    expected: group_anchor_entry.group_id.clone(),
+                }.to_message() )
+            }
+
+            // Check that the Moderator Action is a removal of the target subject
+            if moderator_action_entry.subject_id != subject_id {
+                invalid!( AppStoreError::SubjectMismatch {
+                    context: "Moderator Action".to_string(),
+                    subject: moderator_action_entry.subject_id.clone(),
+                    expected: subject_id,
+                }.to_message() )
+            }
+
+            if moderator_action_entry.action_type() != ModeratorActionType::Remove {
+                invalid!( AppStoreError::NotARemoval {
+                    moderator_action: moderator_action_id,
+                    action_type: moderator_action_entry.action_type(),
+                }.to_message() )
+            }
+
+            let group_entry : GroupEntry = must_get_valid_record(
+                moderator_action_entry.group_id.1.clone()
+            )?.try_into()?;
+
+            // Check that the link author is a contributor in the Moderator Action group revision
+            if !group_entry.contributors().contains( &create.author ) {
+                invalid!( AppStoreError::NotGroupContributor {
+                    agent: create.author.clone(),
+                    group: moderator_action_entry.group_id.0.clone(),
+                    revision: moderator_action_entry.group_id.1.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::ApphubHrlHashToApp => {
+            let app_id = target_address.must_be_action_hash()?;
+
+            // A non-empty tag is the App revision that changed the package
+            let revision = match tag.0.is_empty() {
+                true => app_id.clone(),
+                false => {
+                    let revision = ActionHash::try_from_raw_39( tag.0.clone() )
+                        .map_err(|err| guest_error!(format!(
+                            "Invalid App revision in tag: {:?}", err,
+                        )))?;
+
+                    if trace_origin_root( &revision )?.0 != app_id {
+                        invalid!(format!(
+                            "Tag revision ({}) is not an update of the target App ({})",
+                            revision, app_id,
+                        ))
+                    }
+
+                    revision
+                },
+            };
+            let app_entry : AppEntry = must_get_valid_record( revision )?.try_into()?;
+
+            // Base address must be the DevHub package hash claimed by the app
+            if base_address != app_entry.apphub_hrl_hash.clone().into() {
+                invalid!(format!(
+                    "Base address ({}) must be the App's apphub_hrl_hash ({})",
+                    base_address, app_entry.apphub_hrl_hash,
+                ))
+            }
+
+            // Link author must be in app editors
+            if !app_entry.editors.contains( &create.author ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Link,
+                    agent: create.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::DnaToAppVersion => {
+            let app_version_record = must_get_valid_record(
+                target_address.must_be_action_hash()?
+            )?;
+            // The delegation must cover the version's own action (not this link)
+            let version_timestamp = app_version_record.action().timestamp();
+            let app_version_entry : AppVersionEntry = app_version_record.try_into()?;
+            let app_entry : AppEntry = must_get_valid_record(
+                app_version_entry.for_app.clone()
+            )?.try_into()?;
+
+            // Base address must be one of the app version's DNAs
+            let included = app_version_entry.all_dna_hashes().iter()
+                .any( |dna_hash| base_address == dna_link_base( dna_hash ).into() );
+
+            if !included {
+                invalid!(format!(
+                    "Base address ({}) is not a DNA of the App Version: {:?}",
+                    base_address, app_version_entry.all_dna_hashes(),
+                ))
+            }
+
+            // Link author must be in app editors (or be the version's delegated publisher)
+            if !app_entry.editors.contains( &create.author )
+                && !( app_version_entry.author == create.author
+                       && validate_app_version_publisher(
+                           &app_version_entry, &create.author, &version_timestamp
+                       ).is_ok() ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Link,
+                    agent: create.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::AppVersionToMirror => {
+            let app_version_entry : AppVersionEntry = must_get_valid_record(
+                base_address.must_be_action_hash()?
+            )?.try_into()?;
+            let memory : MemoryEntry = must_get_entry(
+                target_address.must_be_entry_hash()?
+            )?.try_into()?;
+
+            // Mirrored memory must be the exact webhapp bundle of the App Version
+            if memory.hash != app_version_entry.bundle_hashes.hash {
+                invalid!(format!(
+                    "Mirrored memory hash does not match the App Version bundle hash: {} != {}",
+                    memory.hash, app_version_entry.bundle_hashes.hash,
+                ))
+            }
+
+            valid!()
+        },
+        LinkTypes::AppToPublishingDelegation => {
+            let app_id = base_address.must_be_action_hash()?;
+            let app_entry : AppEntry = must_get_valid_record(
+                app_id.clone()
+            )?.try_into()?;
+            let delegation_entry : PublishingDelegationEntry = must_get_valid_record(
+                target_address.must_be_action_hash()?
+            )?.try_into()?;
+
+            // Delegation must belong to app base address
+            if delegation_entry.for_app != app_id {
+                invalid!(format!(
+                    "App base address does not match the app reference in delegation entry: {} != {}",
+                    app_id, delegation_entry.for_app,
+                ))
+            }
+
+            // Link author must be in app editors
+            if !app_entry.editors.contains( &create.author ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Link,
+                    agent: create.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::ChangeLogToAction => {
+            let change = parse_change_log_tag( &tag )?;
+            let change_action_hash = target_address.must_be_action_hash()?;
+            let change_action = must_get_action( change_action_hash.clone() )?;
+            let timestamp = change_action.action().timestamp().as_micros() as u64 / 1_000;
+
+            // Tagged time must be the change action's time (the link may be committed later)
+            if change.timestamp != timestamp {
+                invalid!(format!(
+                    "Change-log tag time ({}) is not the time of the change action ({})",
+                    change.timestamp, timestamp,
+                ))
+            }
+
+            // Base address must be the change-log bucket of the change action's time
+            if base_address != change_log_anchor( timestamp )?.into() {
+                invalid!(format!(
+                    "Base address ({}) is not the change-log anchor for time {}",
+                    base_address, timestamp,
+                ))
+            }
+
+            // Link author must be the author of the change
+            if change_action.action().author() != &create.author {
+                invalid!(format!(
+                    "Link author ({}) is not the author of the change action ({})",
+                    create.author, change_action.action().author(),
+                ))
+            }
+
+            // The revision whose origin must be the subject (the deleted action for a delete)
+            let change_kind = ( change.change_type, change_action.action() );
+            let (entity_address, revision) : (AnyLinkableHash, ActionHash) = match change_kind {
+                ( ChangeType::Create, Action::Create(_) ) |
+                ( ChangeType::Update, Action::Update(_) ) |
+                ( ChangeType::Deprecate, Action::Update(_) ) => (
+                    change_action_hash.clone().into(),
+                    change_action_hash.clone(),
+                ),
+                ( ChangeType::Delete, Action::Delete(delete) ) => (
+                    delete.deletes_address.clone().into(),
+                    delete.deletes_address.clone(),
+                ),
+                ( change_type, action ) => invalid!(format!(
+                    "Change type {:?} does not match action type {:?}",
+                    change_type, action.action_type(),
+                )),
+            };
+
+            // Subject ID must be the entity that the changed revision belongs to
+            let (origin_id, _) = trace_origin_root( &revision )?;
+
+            if change.subject_id != origin_id {
+                invalid!(format!(
+                    "Subject ID ({}) is not the origin ({}) of the changed action ({})",
+                    change.subject_id, origin_id, revision,
+                ))
+            }
+
+            // The changed entry must be of the tagged subject type
+            match change.subject_type {
+                ChangeSubjectType::Publisher => { verify_app_entry_struct::<PublisherEntry>( &entity_address )?; },
+                ChangeSubjectType::App => { verify_app_entry_struct::<AppEntry>( &entity_address )?; },
+                ChangeSubjectType::AppVersion => { verify_app_entry_struct::<AppVersionEntry>( &entity_address )?; },
+            };
+
+            valid!()
+        },
+        LinkTypes::GovernanceToGroup => {
+            if base_address != GOVERNANCE_ANCHOR.path_entry_hash()?.into() {
+                invalid!( AppStoreError::InvalidLinkBase {
+                    link_type: "GovernanceToGroup".to_string(),
+                    base: base_address,
+                    expected: GOVERNANCE_ANCHOR.path_entry_hash()?.into(),
+                }.to_message() )
+            }
+
+            // Link author must be one of the governance agents in the DNA properties
+            let governance_agents = governance_agents()?;
+
+            if !governance_agents.contains( &create.author ) {
+                invalid!( AppStoreError::NotGovernanceAgent {
+                    agent: create.author.clone(),
+                    governance_agents,
+                }.to_message() )
+            }
+
+            let group_id = target_address.must_be_action_hash()?;
+
+            if !is_governance_group( &group_id )? {
+                invalid!( AppStoreError::NotGovernanceGroup {
+                    group: group_id,
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::AppVersionToBundleVerification => {
+            let verification_entry : BundleVerificationEntry = must_get_valid_record(
+                target_address.must_be_action_hash()?
+            )?.try_into()?;
+
+            // Base address must be the verified App Version
+            if base_address != verification_entry.app_version.clone().into() {
+                invalid!(format!(
+                    "Base address ({}) must be the verified App Version ({})",
+                    base_address, verification_entry.app_version,
+                ))
+            }
+
+            // Link author must be the verifier
+            if verification_entry.author != create.author {
+                invalid!(format!(
+                    "Link author ({}) is not the Bundle Verification author ({})",
+                    create.author, verification_entry.author,
+                ))
+            }
+
+            valid!()
+        },
         // _ => invalid!(format!("Create link validation not implemented for link type: {:#?}", create.link_type )),
     }
 }
@@ -1,11 +1,14 @@
 use crate::{
     hdi,
     hdi_extensions,
+    AppStoreError,
+    AuthorRole,
     EntryTypesUnit,
 
     PublisherEntry,
     AppEntry,
     AppVersionEntry,
+    PublishingDelegationEntry,
 };
 
 use hdi::prelude::*;
@@ -37,10 +40,11 @@ pub fn validation(
 
             // Allow any publisher editor
             if !publisher_entry.editors.contains( &delete.author ) {
-                invalid!(format!(
-                    "Delete author ({}) is not in editor list: {:?}",
-                    delete.author, publisher_entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: publisher_entry.editors.clone(),
+                }.to_message() )
             }
 
             valid!()
@@ -52,10 +56,11 @@ pub fn validation(
 
             // Allow any app editor
             if !app_entry.editors.contains( &delete.author ) {
-                invalid!(format!(
-                    "Delete author ({}) is not in editor list: {:?}",
-                    delete.author, app_entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
             }
 
             valid!()
@@ -70,25 +75,75 @@ pub fn validation(
 
             // Allow any app editor
             if !app_entry.editors.contains( &delete.author ) {
-                invalid!(format!(
-                    "Delete author ({}) is not in editor list: {:?}",
-                    delete.author, app_entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
             }
 
             valid!()
         },
         EntryTypesUnit::ModeratorAction => {
-            invalid!(format!(
-                "Not authorized to delete moderator action created by {}",
-                create.author,
-            ))
+            invalid!( AppStoreError::UnauthorizedDelete {
+                entry_type: "moderator action".to_string(),
+                author: create.author.clone(),
+            }.to_message() )
         },
         EntryTypesUnit::GroupAnchor => {
-            invalid!(format!(
-                "Not authorized to delete group anchor created by {}",
-                create.author,
-            ))
+            invalid!( AppStoreError::UnauthorizedDelete {
+                entry_type: "group anchor".to_string(),
+                author: create.author.clone(),
+            }.to_message() )
+        },
+        EntryTypesUnit::Appeal => {
+            invalid!( AppStoreError::UnauthorizedDelete {
+                entry_type: "appeal".to_string(),
+                author: create.author.clone(),
+            }.to_message() )
+        },
+        EntryTypesUnit::AppealResolution => {
+            invalid!( AppStoreError::UnauthorizedDelete {
+                entry_type: "appeal resolution".to_string(),
+                author: create.author.clone(),
+            }.to_message() )
+        },
+        EntryTypesUnit::ViewpointPreference => {
+            invalid!( AppStoreError::UnauthorizedDelete {
+                entry_type: "viewpoint preference".to_string(),
+                author: create.author.clone(),
+            }.to_message() )
+        },
+        EntryTypesUnit::HostLiveness => {
+            invalid!( AppStoreError::UnauthorizedDelete {
+                entry_type: "host liveness record".to_string(),
+                author: create.author.clone(),
+            }.to_message() )
+        },
+        EntryTypesUnit::PublishingDelegation => {
+            let delegation_entry : PublishingDelegationEntry = must_get_valid_record(
+                original_action_hash,
+            )?.try_into()?;
+            let app_entry : AppEntry = must_get_valid_record(
+                delegation_entry.for_app,
+            )?.try_into()?;
+
+            // Allow any app editor to revoke
+            if !app_entry.editors.contains( &delete.author ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        EntryTypesUnit::BundleVerification => {
+            invalid!( AppStoreError::UnauthorizedDelete {
+                entry_type: "bundle verification".to_string(),
+                author: create.author.clone(),
+            }.to_message() )
         },
         // entry_type_unit => invalid!(format!("Delete validation not implemented for entry type: {:?}", entry_type_unit )),
     }
@@ -1,10 +1,15 @@
 use crate::{
     hdi,
     hdi_extensions,
+    AppStoreError,
+    AuthorRole,
     LinkTypes,
+    parse_removed_subject_tag,
+    governance_agents,
 
     PublisherEntry,
     AppEntry,
+    AppVersionEntry,
     ModeratorActionEntry,
 
     coop_content_sdk::{
@@ -36,6 +41,11 @@ pub fn validation(
         None => invalid!(format!("No match for LinkTypes")),
     };
 
+    // The change log is append-only, even for the link creator
+    if let LinkTypes::ChangeLogToAction = link_type {
+        invalid!(format!("Change-log links cannot be deleted"))
+    }
+
     // Always allow link creator to delete their link
     if create_link.author == delete.author {
         valid!()
@@ -88,10 +98,11 @@ pub fn validation(
 
             // Allow any publisher editor
             if !publisher_entry.editors.contains( &delete.author ) {
-                invalid!(format!(
-                    "Delete author ({}) is not in editor list: {:?}",
-                    delete.author, publisher_entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: publisher_entry.editors.clone(),
+                }.to_message() )
             }
 
             valid!()
@@ -110,10 +121,11 @@ pub fn validation(
 
             // Allow any app editor
             if !app_entry.editors.contains( &delete.author ) {
-                invalid!(format!(
-                    "Delete author ({}) is not in editor list: {:?}",
-                    delete.author, app_entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
             }
 
             valid!()
@@ -138,5 +150,123 @@ pub fn validation(
 
             valid!()
         },
+        LinkTypes::GroupAnchorToRemovedSubject => {
+            let (_, moderator_action_id) = parse_removed_subject_tag( &create_link.tag )?;
+
+            let moderator_action_entry : ModeratorActionEntry = must_get_valid_record(
+                moderator_action_id
+            )?.try_into()?;
+            let group_entry : GroupEntry = must_get_valid_record(
+                moderator_action_entry.group_id.1.clone()
+            )?.try_into()?;
+
+            // Check that the action author is a contributor in the Moderator Action group revision
+            if !group_entry.contributors().contains( &delete.author ) {
+                invalid!(format!(
+                    "Delete author ({}) is not a contributor in Group ({}) revision ({})",
+                    delete.author, moderator_action_entry.group_id.0, moderator_action_entry.group_id.1,
+                ))
+            }
+
+            valid!()
+        },
+        LinkTypes::ModeratorActionToAppeal => {
+            invalid!(format!(
+                "Only the link creator ({}) can delete a Moderator Action -> Appeal link",
+                create_link.author,
+            ))
+        },
+        LinkTypes::GroupAnchorToAppeal => {
+            invalid!(format!(
+                "Only the link creator ({}) can delete a Group Anchor -> Appeal link",
+                create_link.author,
+            ))
+        },
+        LinkTypes::AppealToAppealResolution => {
+            invalid!(format!(
+                "Only the link creator ({}) can delete an Appeal -> Appeal Resolution link",
+                create_link.author,
+            ))
+        },
+        LinkTypes::ApphubHrlHashToApp => {
+            let app_id = create_link.target_address.must_be_action_hash()?;
+            let app_entry : AppEntry = must_get_valid_record(
+                app_id
+            )?.try_into()?;
+
+            // Allow any app editor
+            if !app_entry.editors.contains( &delete.author ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::DnaToAppVersion => {
+            let app_version_entry : AppVersionEntry = must_get_valid_record(
+                create_link.target_address.must_be_action_hash()?
+            )?.try_into()?;
+            let app_entry : AppEntry = must_get_valid_record(
+                app_version_entry.for_app
+            )?.try_into()?;
+
+            // Allow any app editor
+            if !app_entry.editors.contains( &delete.author ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::AppVersionToMirror => {
+            invalid!(format!(
+                "Only the link creator ({}) can delete an App Version -> Mirror link",
+                create_link.author,
+            ))
+        },
+        LinkTypes::AppToPublishingDelegation => {
+            let app_entry : AppEntry = must_get_valid_record(
+                create_link.base_address.clone().must_be_action_hash()?
+            )?.try_into()?;
+
+            // Allow any app editor
+            if !app_entry.editors.contains( &delete.author ) {
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Delete,
+                    agent: delete.author.clone(),
+                    editors: app_entry.editors.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::ChangeLogToAction => {
+            invalid!(format!("Change-log links cannot be deleted"))
+        },
+        LinkTypes::GovernanceToGroup => {
+            let governance_agents = governance_agents()?;
+
+            // Allow any governance agent
+            if !governance_agents.contains( &delete.author ) {
+                invalid!( AppStoreError::NotGovernanceAgent {
+                    agent: delete.author.clone(),
+                    governance_agents,
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        LinkTypes::AppVersionToBundleVerification => {
+            invalid!(format!(
+                "Only the link creator ({}) can delete a bundle verification link",
+                create_link.author,
+            ))
+        },
     }
 }
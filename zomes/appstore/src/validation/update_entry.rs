@@ -1,9 +1,18 @@
 use crate::{
     hdi,
     hdi_extensions,
+    AppStoreError,
+    AuthorRole,
 
     validate_common_fields_create,
     validate_icon_field,
+    validate_governance_auth,
+    validate_manifest_summary,
+    validate_apphub_hrl,
+    validate_source_ownership,
+    validate_app_version_publisher,
+    validate_schema_version,
+    validate_provenance,
 
     EntryTypes,
     PublisherEntry,
@@ -18,6 +27,7 @@ use crate::{
 use hdi::prelude::*;
 use hdi_extensions::{
     guest_error,
+    trace_origin_root,
     valid, invalid,
 };
 
@@ -25,7 +35,7 @@ use hdi_extensions::{
 pub fn validation(
     app_entry: EntryTypes,
     update: Update,
-    _original_action_hash: ActionHash,
+    original_action_hash: ActionHash,
     original_entry_hash: EntryHash
 ) -> ExternResult<ValidateCallbackResult> {
     match app_entry {
@@ -35,28 +45,43 @@ pub fn validation(
 
             // Check that the editors list did not change
             if previous_entry.editors != entry.editors {
-                invalid!(format!(
-                    "Cannot update the editors list: {:?} => {:?}",
-                    previous_entry.editors, entry.editors,
-                ))
+                invalid!( AppStoreError::EditorsChanged {
+                    previous: previous_entry.editors.clone(),
+                    next: entry.editors.clone(),
+                }.to_message() )
             }
 
             // Check that the entry is not deprecated
             if entry.deprecation.is_some() && previous_entry.deprecation.is_some() {
-                invalid!(format!(
-                    "Cannot update deprecated entity unless the deprecation is being reversed",
-                ))
+                invalid!( AppStoreError::DeprecatedEntity.to_message() )
             }
 
             // Check author field matches action author
             validate_common_fields_create( &update, &entry )?;
 
+            // Check that the schema version is supported and not downgraded
+            validate_schema_version( &entry, Some( &previous_entry ) )?;
+
+            // Check that the origin of an imported entity is not changed
+            if previous_entry.provenance != entry.provenance {
+                invalid!( AppStoreError::ImmutableField {
+                    entry_type: "Publisher".to_string(),
+                    field: "provenance".to_string(),
+                    previous: format!("{:?}", previous_entry.provenance ),
+                    next: format!("{:?}", entry.provenance ),
+                }.to_message() )
+            }
+
+            // Check that a provenance points at a previous DNA
+            validate_provenance( &entry.provenance )?;
+
             // Check that the author field is in the editors list
             if !entry.editors.contains( &entry.author ) {
-                invalid!(format!(
-                    "Entry author ({}) must be in the editors list: {:?}",
-                    entry.author, entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Entry,
+                    agent: entry.author.clone(),
+                    editors: entry.editors.clone(),
+                }.to_message() )
             }
 
             // Check icon size
@@ -72,33 +97,54 @@ pub fn validation(
 
             // Check that the editors list did not change
             if previous_entry.editors != entry.editors {
-                invalid!(format!(
-                    "Cannot update the editors list: {:?} => {:?}",
-                    previous_entry.editors, entry.editors,
-                ))
+                invalid!( AppStoreError::EditorsChanged {
+                    previous: previous_entry.editors.clone(),
+                    next: entry.editors.clone(),
+                }.to_message() )
             }
 
             // Check that the entry is not deprecated
             if entry.deprecation.is_some() && previous_entry.deprecation.is_some() {
-                invalid!(format!(
-                    "Cannot update deprecated entity unless the deprecation is being reversed",
-                ))
+                invalid!( AppStoreError::DeprecatedEntity.to_message() )
             }
 
             // Check author field matches action author
             validate_common_fields_create( &update, &entry )?;
 
+            // Check that the schema version is supported and not downgraded
+            validate_schema_version( &entry, Some( &previous_entry ) )?;
+
+            // Check that the origin of an imported entity is not changed
+            if previous_entry.provenance != entry.provenance {
+                invalid!( AppStoreError::ImmutableField {
+                    entry_type: "App".to_string(),
+                    field: "provenance".to_string(),
+                    previous: format!("{:?}", previous_entry.provenance ),
+                    next: format!("{:?}", entry.provenance ),
+                }.to_message() )
+            }
+
+            // Check that a provenance points at a previous DNA
+            validate_provenance( &entry.provenance )?;
+
             // Check that this action author is in the editor list of the previous publisher entry
             if !entry.editors.contains( &update.author ) {
-                invalid!(format!(
-                    "Update author ({}) must be in the editors list: {:?}",
-                    update.author, entry.editors,
-                ))
+                invalid!( AppStoreError::UnauthorizedEditor {
+                    role: AuthorRole::Update,
+                    agent: update.author.clone(),
+                    editors: entry.editors.clone(),
+                }.to_message() )
             }
 
             // Check icon size
             validate_icon_field( &entry.icon, "AppEntry" )?;
 
+            // Check that the HRL agrees with its hash and points at an allowed DevHub
+            validate_apphub_hrl( &entry.apphub_hrl, &entry.apphub_hrl_hash )?;
+
+            // Check that an ownership proof was signed for this listing
+            validate_source_ownership( &entry )?;
+
             valid!()
         },
         EntryTypes::AppVersion(entry) => {
@@ -108,61 +154,100 @@ pub fn validation(
                 previous_entry.for_app.clone()
             )?.try_into()?;
 
-            // Check that this action author is in the editor list of the previous publisher entry
+            // Check that this action author is an App editor or the version's original author
+            // publishing under a delegation
             if !app_entry.editors.contains( &update.author ) {
-                invalid!(format!(
-                    "Update author ({}) must be in the App's editors list: {:?}",
-                    update.author, app_entry.editors,
-                ))
+                let (origin_id, _) = trace_origin_root( &original_action_hash )?;
+                let origin_action = must_get_action( origin_id )?;
+
+                if *origin_action.action().author() != update.author {
+                    invalid!( AppStoreError::UnauthorizedEditor {
+                        role: AuthorRole::Update,
+                        agent: update.author.clone(),
+                        editors: app_entry.editors.clone(),
+                    }.to_message() )
+                }
+
+                validate_app_version_publisher( &entry, &update.author, &update.timestamp )?;
             }
 
             // Check author field matches action author
             validate_common_fields_create( &update, &entry )?;
 
+            // Check that the schema version is supported and not downgraded
+            validate_schema_version( &entry, Some( &previous_entry ) )?;
+
+            // Check that the origin of an imported entity is not changed
+            if previous_entry.provenance != entry.provenance {
+                invalid!( AppStoreError::ImmutableField {
+                    entry_type: "App version".to_string(),
+                    field: "provenance".to_string(),
+                    previous: format!("{:?}", previous_entry.provenance ),
+                    next: format!("{:?}", entry.provenance ),
+                }.to_message() )
+            }
+
+            // Check that a provenance points at a previous DNA
+            validate_provenance( &entry.provenance )?;
+
             // Fields that cannot be changed
             if previous_entry.for_app != entry.for_app {
-                invalid!(format!(
-                    "App version field 'for_app' cannot be updated: {} => {}",
-                    previous_entry.for_app, entry.for_app,
-                ))
+                invalid!( AppStoreError::ImmutableField {
+                    entry_type: "App version".to_string(),
+                    field: "for_app".to_string(),
+                    previous: format!("{}", previous_entry.for_app ),
+                    next: format!("{}", entry.for_app ),
+                }.to_message() )
             }
 
             if previous_entry.apphub_hrl != entry.apphub_hrl {
-                invalid!(format!(
-                    "App version field 'apphub_hrl' cannot be updated: {:?} => {:?}",
-                    previous_entry.apphub_hrl, entry.apphub_hrl,
-                ))
+                invalid!( AppStoreError::ImmutableField {
+                    entry_type: "App version".to_string(),
+                    field: "apphub_hrl".to_string(),
+                    previous: format!("{:?}", previous_entry.apphub_hrl ),
+                    next: format!("{:?}", entry.apphub_hrl ),
+                }.to_message() )
             }
 
             if previous_entry.apphub_hrl_hash != entry.apphub_hrl_hash {
-                invalid!(format!(
-                    "App version field 'apphub_hrl_hash' cannot be updated: {} => {}",
-                    previous_entry.apphub_hrl_hash, entry.apphub_hrl_hash,
-                ))
+                invalid!( AppStoreError::ImmutableField {
+                    entry_type: "App version".to_string(),
+                    field: "apphub_hrl_hash".to_string(),
+                    previous: format!("{}", previous_entry.apphub_hrl_hash ),
+                    next: format!("{}", entry.apphub_hrl_hash ),
+                }.to_message() )
             }
 
             if previous_entry.bundle_hashes != entry.bundle_hashes {
-                invalid!(format!(
-                    "App version field 'bundle_hashes' cannot be updated: {:?} => {:?}",
-                    previous_entry.bundle_hashes, entry.bundle_hashes,
-                ))
+                invalid!( AppStoreError::ImmutableField {
+                    entry_type: "App version".to_string(),
+                    field: "bundle_hashes".to_string(),
+                    previous: format!("{:?}", previous_entry.bundle_hashes ),
+                    next: format!("{:?}", entry.bundle_hashes ),
+                }.to_message() )
             }
 
+            // Check that a manifest summary agrees with the bundle hashes
+            validate_manifest_summary( &entry )?;
+
             valid!()
         },
         EntryTypes::ModeratorAction(entry) => {
             // Check author field matches action author
             if entry.author != update.author {
-                invalid!(format!(
-                    "Entry author does not match Action author: {} != {}",
-                    entry.author, update.author
-                ));
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: update.author.clone(),
+                }.to_message() )
             }
 
             // Check that the author is a contributor to the claimed group
-            validate_group_auth( &entry, update )
+            validate_group_auth( &entry, update.clone() )
                 .map_err(|err| guest_error!(err) )?;
 
+            // Check that governance actions are issued by the governance group's contributors
+            validate_governance_auth( &entry, &update.author )?;
+
             valid!()
         },
         EntryTypes::GroupAnchor(entry) => {
@@ -172,6 +257,48 @@ pub fn validation(
 
             valid!()
         },
+        EntryTypes::Appeal(_) => {
+            invalid!( AppStoreError::ImmutableEntry {
+                entry_type: "Appeals".to_string(),
+            }.to_message() )
+        },
+        EntryTypes::AppealResolution(_) => {
+            invalid!( AppStoreError::ImmutableEntry {
+                entry_type: "Appeal Resolutions".to_string(),
+            }.to_message() )
+        },
+        EntryTypes::ViewpointPreference(entry) => {
+            // Check author field matches action author
+            if entry.author != update.author {
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: update.author.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        EntryTypes::HostLiveness(entry) => {
+            // Check author field matches action author
+            if entry.author != update.author {
+                invalid!( AppStoreError::AuthorMismatch {
+                    entry_author: entry.author.clone(),
+                    action_author: update.author.clone(),
+                }.to_message() )
+            }
+
+            valid!()
+        },
+        EntryTypes::PublishingDelegation(_) => {
+            invalid!( AppStoreError::ImmutableEntry {
+                entry_type: "Publishing delegations".to_string(),
+            }.to_message() )
+        },
+        EntryTypes::BundleVerification(_) => {
+            invalid!( AppStoreError::ImmutableEntry {
+                entry_type: "Bundle verifications".to_string(),
+            }.to_message() )
+        },
         // _ => invalid!(format!("Update validation not implemented for entry type: {:#?}", update.entry_type )),
     }
 }
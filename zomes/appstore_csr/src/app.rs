@@ -1,16 +1,33 @@
 use crate::{
     hdk,
+    hdi_extensions,
+    governance::{
+        is_governance_removed,
+    },
+    apphub::{
+        verify_apphub_hrl_target,
+        sign_source_ownership_claim,
+    },
+    change_log::{
+        record_change,
+    },
 };
 
 use std::collections::BTreeMap;
 use hdk::prelude::*;
 use hdk_extensions::{
     agent_id,
+    must_get,
+};
+use hdi_extensions::{
+    AnyLinkableHashTransformer,
 };
 use appstore::{
+    SchemaVersioned,
     LinkTypes,
-    RmpvValue,
-    HRL,
+    ChangeSubjectType,
+    ChangeType,
+    AppStoreError,
     DeprecationNotice,
 
     ALL_APPS_ANCHOR,
@@ -19,31 +36,23 @@ use appstore::{
     hc_crud::{
         now, create_entity, get_entity, update_entity,
         Entity,
-        EntityId,
-        GetEntityInput, UpdateEntityInput,
+        GetEntityInput,
     },
 };
+use apphub_sdk::{
+    apphub_types::{
+        WebAppPackageEntry,
+    },
+};
+pub use appstore_sdk::{
+    CreateAppInput as CreateInput,
+    UpdateAppProperties as UpdateProperties,
+    UpdateAppInput as UpdateInput,
+    DeprecateAppInput as DeprecateInput,
+    UndeprecateAppInput as UndeprecateInput,
+};
 
 
-
-#[derive(Debug, Deserialize)]
-pub struct CreateInput {
-    pub title: String,
-    pub subtitle: String,
-    pub description: String,
-    pub icon: EntryHash,
-    pub publisher: EntityId,
-    pub apphub_hrl: HRL,
-    pub apphub_hrl_hash: EntryHash,
-
-    // optional
-    pub editors: Option<Vec<AgentPubKey>>,
-
-    pub published_at: Option<u64>,
-    pub last_updated: Option<u64>,
-    pub metadata: Option<BTreeMap<String, RmpvValue>>,
-}
-
 #[hdk_extern]
 pub fn create_app(mut input: CreateInput) -> ExternResult<Entity<AppEntry>> {
     debug!("Creating App: {}", input.title );
@@ -57,7 +66,7 @@ pub fn create_app(mut input: CreateInput) -> ExternResult<Entity<AppEntry>> {
 	}
     }
 
-    let app = AppEntry {
+    let mut app = AppEntry {
 	title: input.title,
 	subtitle: input.subtitle,
 	description: input.description,
@@ -79,7 +88,25 @@ pub fn create_app(mut input: CreateInput) -> ExternResult<Entity<AppEntry>> {
 	    .unwrap_or( BTreeMap::new() ),
 
 	deprecation: None,
+	source_ownership: None,
+	provenance: input.provenance,
+
+	schema_version: AppEntry::SCHEMA_VERSION,
     };
+
+    // A given proof is checked against its included package by validation
+    if let Some(proof) = input.source_ownership {
+	app.source_ownership = Some( proof );
+    }
+    else if input.prove_source_ownership.unwrap_or(false) {
+	app.source_ownership = Some( sign_source_ownership_claim( app.source_ownership_claim() )? );
+    }
+
+    verify_apphub_hrl_target::<WebAppPackageEntry,_>(
+	&app.apphub_hrl, &app.apphub_hrl_hash, "get_webapp_package_entry"
+    )?;
+    check_apphub_hrl_unclaimed( &app.apphub_hrl_hash, None, app.source_ownership_proven() )?;
+
     let entity = create_entity( &app )?;
 
     { // Path via Agent's Apps
@@ -105,39 +132,162 @@ pub fn create_app(mut input: CreateInput) -> ExternResult<Entity<AppEntry>> {
             None
         )?;
     }
+    { // Path via DevHub package claim
+	entity.link_from(
+            &entity.content.apphub_hrl_hash,
+            LinkTypes::ApphubHrlHashToApp,
+            None
+        )?;
+    }
+    { // Path via Change Log
+	record_change( ChangeSubjectType::App, ChangeType::Create, &entity.id, &entity.action )?;
+    }
 
     Ok( entity )
 }
 
 
+/// A link claiming a DevHub package hash for an App
+pub(crate) struct ApphubHrlClaim {
+    pub link: Link,
+    pub app_id: ActionHash,
+    /// The App carries the package maintainer's [`appstore::SourceOwnershipProof`]
+    pub proven: bool,
+}
+
+
+/// Get the claims for a DevHub package hash, winning claim first
+///
+/// Package claims are advisory: validation cannot see every claim link, so first-claim-wins is
+/// applied when reading.  Link timestamps are chosen by their authors, so a claim backed by the
+/// package maintainer's proof outranks any unproven claim; ties go to the earliest link then the
+/// lowest App ID.
+pub(crate) fn get_apphub_hrl_claims(apphub_hrl_hash: &EntryHash) -> ExternResult<Vec<ApphubHrlClaim>> {
+    let claim_links = get_links(
+        GetLinksInputBuilder::try_new(
+            apphub_hrl_hash.clone(),
+            LinkTypes::ApphubHrlHashToApp,
+        )?.build()
+    )?;
+    let mut claims = vec![];
+
+    for link in claim_links {
+	let app_id = link.target.must_be_action_hash()?;
+	let proven = match get_entity::<AppEntry>( &app_id ) {
+	    // Skip claims left behind by an App that moved to another package
+	    Ok(app) if app.content.apphub_hrl_hash != *apphub_hrl_hash => continue,
+	    Ok(app) => app.content.source_ownership_proven(),
+	    Err(_) => false,
+	};
+
+	claims.push( ApphubHrlClaim {
+	    link,
+	    app_id,
+	    proven,
+	});
+    }
+
+    claims.sort_by(|a, b| {
+	b.proven.cmp( &a.proven )
+	    .then_with( || a.link.timestamp.cmp( &b.link.timestamp ) )
+	    .then_with( || a.app_id.cmp( &b.app_id ) )
+    });
+
+    Ok( claims )
+}
+
+
+/// Fail if a DevHub package hash is already claimed by an App other than `app_id`
+///
+/// A `proven` claim only yields to other proven claims.
+pub(crate) fn check_apphub_hrl_unclaimed(
+    apphub_hrl_hash: &EntryHash,
+    app_id: Option<&ActionHash>,
+    proven: bool,
+) -> ExternResult<()> {
+    for claim in get_apphub_hrl_claims( apphub_hrl_hash )? {
+	if Some( &claim.app_id ) != app_id && ( claim.proven || !proven ) {
+	    return Err( AppStoreError::PackageAlreadyClaimed {
+		apphub_hrl_hash: apphub_hrl_hash.clone(),
+		app: claim.app_id.clone(),
+	    }.into() );
+	}
+    }
+
+    Ok(())
+}
+
+
+/// Add the DevHub package claim link for each of this agent's Apps that is missing one
+///
+/// Apps created before claim links existed are not in the index.  Returns the number of claims
+/// added.
+#[hdk_extern]
+pub fn backfill_apphub_hrl_claims(_: ()) -> ExternResult<usize> {
+    let mut count = 0;
+
+    for app in crate::get_my_apps(())? {
+	let claimed = get_links(
+	    GetLinksInputBuilder::try_new(
+		app.content.apphub_hrl_hash.clone(),
+		LinkTypes::ApphubHrlHashToApp,
+	    )?.build()
+	)?.into_iter()
+	    .any( |link| link.target == app.id.clone().into() );
+
+	if claimed {
+	    continue;
+	}
+
+	let tag = match app.action == app.id {
+	    true => None,
+	    false => Some( app.action.get_raw_39().to_vec() ),
+	};
+
+	app.link_from( &app.content.apphub_hrl_hash, LinkTypes::ApphubHrlHashToApp, tag )?;
+	count += 1;
+    }
+
+    Ok( count )
+}
+
+
+#[hdk_extern]
+pub fn get_app_for_apphub_hrl(apphub_hrl_hash: EntryHash) -> ExternResult<Entity<AppEntry>> {
+    debug!("Get app for DevHub package: {}", apphub_hrl_hash );
+    let claim = get_apphub_hrl_claims( &apphub_hrl_hash )?
+	.into_iter()
+	.next()
+	.ok_or( AppStoreError::UnclaimedPackage {
+	    apphub_hrl_hash: apphub_hrl_hash.clone(),
+	})?;
+
+    get_app( GetEntityInput {
+	id: claim.app_id,
+    })
+}
+
+
 #[hdk_extern]
 pub fn get_app(input: GetEntityInput) -> ExternResult<Entity<AppEntry>> {
     debug!("Get app: {}", input.id );
     let entity : Entity<AppEntry> = get_entity( &input.id )?;
 
+    if is_governance_removed( &entity.id )? {
+	return Err( AppStoreError::RemovedByGovernance {
+	    app: entity.id.clone(),
+	}.into() );
+    }
+
     Ok(	entity )
 }
 
 
-#[derive(Debug, Deserialize, Clone)]
-pub struct UpdateProperties {
-    pub title: Option<String>,
-    pub subtitle: Option<String>,
-    pub description: Option<String>,
-    pub icon: Option<EntryHash>,
-    pub apphub_hrl: Option<HRL>,
-    pub apphub_hrl_hash: Option<EntryHash>,
-    pub editors: Option<Vec<AgentPubKey>>,
-    pub published_at: Option<u64>,
-    pub last_updated: Option<u64>,
-    pub metadata: Option<BTreeMap<String, RmpvValue>>,
-}
-pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
-
 #[hdk_extern]
 pub fn update_app(input: UpdateInput) -> ExternResult<Entity<AppEntry>> {
     debug!("Updating App: {}", input.base );
     let props = input.properties.clone();
+    let previous : AppEntry = must_get( &input.base )?.try_into()?;
 
     let entity = update_entity(
 	&input.base,
@@ -152,6 +302,13 @@ pub fn update_app(input: UpdateInput) -> ExternResult<Entity<AppEntry>> {
 		.unwrap_or( current.apphub_hrl );
 	    current.apphub_hrl_hash = props.apphub_hrl_hash
 		.unwrap_or( current.apphub_hrl_hash );
+
+	    if current.apphub_hrl != previous.apphub_hrl
+		|| current.apphub_hrl_hash != previous.apphub_hrl_hash {
+		verify_apphub_hrl_target::<WebAppPackageEntry,_>(
+		    &current.apphub_hrl, &current.apphub_hrl_hash, "get_webapp_package_entry"
+		)?;
+	    }
 	    current.icon = props.icon
 		.unwrap_or( current.icon );
 	    current.author = agent_id()?;
@@ -162,19 +319,58 @@ pub fn update_app(input: UpdateInput) -> ExternResult<Entity<AppEntry>> {
 	    current.metadata = props.metadata
 		.unwrap_or( current.metadata );
 
+	    if let Some(proof) = props.source_ownership {
+		current.source_ownership = Some( proof );
+	    }
+	    // A proof only covers the package it was signed for
+	    else if let Some(proof) = current.source_ownership.clone() {
+		if proof.package_hash()? != current.apphub_hrl_hash
+		    || proof.package_maintainer().as_ref() != Some( &proof.signer )
+		    || !verify_signature( proof.signer, proof.signature, current.source_ownership_claim() )? {
+		    current.source_ownership = None;
+		}
+	    }
+
+	    current.upgrade();
+
 	    Ok( current )
 	})?;
 
-    Ok( entity )
-}
+    // Move the DevHub package claim when the package changes
+    if entity.content.apphub_hrl_hash != previous.apphub_hrl_hash {
+	check_apphub_hrl_unclaimed(
+	    &entity.content.apphub_hrl_hash,
+	    Some( &entity.id ),
+	    entity.content.source_ownership_proven(),
+	)?;
+
+	let previous_claims = get_links(
+	    GetLinksInputBuilder::try_new(
+		previous.apphub_hrl_hash.clone(),
+		LinkTypes::ApphubHrlHashToApp,
+	    )?.build()
+	)?;
+
+	for link in previous_claims {
+	    if link.target == entity.id.clone().into() {
+		delete_link( link.create_link_hash, GetOptions::default() )?;
+	    }
+	}
+
+	// Validation reads the package hash from the revision in the tag
+	entity.link_from(
+            &entity.content.apphub_hrl_hash,
+            LinkTypes::ApphubHrlHashToApp,
+            Some( entity.action.get_raw_39().to_vec() )
+        )?;
+    }
 
+    record_change( ChangeSubjectType::App, ChangeType::Update, &entity.id, &entity.action )?;
 
-#[derive(Debug, Deserialize)]
-pub struct DeprecateInput {
-    pub base: ActionHash,
-    pub message: String,
+    Ok( entity )
 }
 
+
 #[hdk_extern]
 pub fn deprecate_app(input: DeprecateInput) -> ExternResult<Entity<AppEntry>> {
     debug!("Deprecating hApp: {}", input.base );
@@ -183,21 +379,20 @@ pub fn deprecate_app(input: DeprecateInput) -> ExternResult<Entity<AppEntry>> {
 	|mut current : AppEntry, _| {
 	    current.deprecation = Some(DeprecationNotice {
 		message: input.message.to_owned(),
-		recommended_alternatives: None,
+		recommended_alternatives: input.recommended_alternatives.to_owned(),
 	    });
 
+	    current.upgrade();
+
 	    Ok( current )
 	})?;
 
+    record_change( ChangeSubjectType::App, ChangeType::Deprecate, &entity.id, &entity.action )?;
+
     Ok( entity )
 }
 
 
-#[derive(Debug, Deserialize)]
-pub struct UndeprecateInput {
-    pub base: ActionHash,
-}
-
 #[hdk_extern]
 pub fn undeprecate_app(input: UndeprecateInput) -> ExternResult<Entity<AppEntry>> {
     debug!("Undeprecating App: {}", input.base );
@@ -206,8 +401,12 @@ pub fn undeprecate_app(input: UndeprecateInput) -> ExternResult<Entity<AppEntry>
 	|mut current : AppEntry, _| {
 	    current.deprecation = None;
 
+	    current.upgrade();
+
 	    Ok( current )
 	})?;
 
+    record_change( ChangeSubjectType::App, ChangeType::Update, &entity.id, &entity.action )?;
+
     Ok( entity )
 }
@@ -1,5 +1,19 @@
 use crate::{
     hdk,
+    apphub::{
+        verify_apphub_hrl_target,
+        verify_bundle_hashes,
+        create_bundle_verification,
+    },
+    app::{
+        get_app,
+    },
+    delegation::{
+        find_publishing_delegation,
+    },
+    change_log::{
+        record_change,
+    },
 };
 
 use std::collections::BTreeMap;
@@ -8,45 +22,44 @@ use hdk_extensions::{
     agent_id,
 };
 use appstore::{
+    SchemaVersioned,
     EntryTypes,
     LinkTypes,
-    RmpvValue,
+    ChangeSubjectType,
+    ChangeType,
+    AppStoreError,
 
-    HRL,
-    BundleHashes,
+    AppEntry,
     AppVersionEntry,
+    dna_link_base,
 
     hc_crud::{
-        now, create_entity, get_entity, update_entity, delete_entity,
+        now, create_entity, get_entity, get_entities, update_entity, delete_entity,
         Entity,
-        EntityId,
-        GetEntityInput, UpdateEntityInput,
+        GetEntityInput,
     },
 };
+use apphub_sdk::{
+    apphub_types::{
+        WebAppPackageVersionEntry,
+    },
+};
+pub use appstore_sdk::{
+    CreateAppVersionInput as CreateInput,
+    UpdateAppVersionProperties as UpdateProperties,
+    UpdateAppVersionInput as UpdateInput,
+    DeleteAppVersionInput as DeleteInput,
+    DnaAppVersion,
+};
 
 
-
-#[derive(Debug, Deserialize)]
-pub struct CreateInput {
-    pub version: String,
-    pub for_app: EntityId,
-    pub apphub_hrl: HRL,
-    pub apphub_hrl_hash: EntryHash,
-    pub bundle_hashes: BundleHashes,
-
-    // optional
-    pub published_at: Option<u64>,
-    pub last_updated: Option<u64>,
-    pub metadata: Option<BTreeMap<String, RmpvValue>>,
-}
-
 #[hdk_extern]
 pub fn create_app_version(input: CreateInput) -> ExternResult<Entity<AppVersionEntry>> {
     debug!("Creating AppVersion: {}", input.version );
     let pubkey = agent_id()?;
     let default_now = now()?;
 
-    let app_version = AppVersionEntry {
+    let mut app_version = AppVersionEntry {
 	version: input.version,
 	for_app: input.for_app.clone(),
 	apphub_hrl: input.apphub_hrl,
@@ -61,17 +74,111 @@ pub fn create_app_version(input: CreateInput) -> ExternResult<Entity<AppVersionE
 
 	metadata: input.metadata
 	    .unwrap_or( BTreeMap::new() ),
+
+	manifest: input.manifest,
+	dna_hashes: input.dna_hashes,
+	delegation: input.delegation,
+	provenance: input.provenance,
+
+	schema_version: AppVersionEntry::SCHEMA_VERSION,
     };
+
+    // Non-editors publish under a delegation issued by one of the App's editors
+    if app_version.delegation.is_none() {
+	let app : Entity<AppEntry> = get_entity( &input.for_app )?;
+
+	if !app.content.editors.contains( &app_version.author ) {
+	    app_version.delegation = find_publishing_delegation(
+		&app_version.for_app,
+		&app_version.author,
+		default_now,
+	    )?;
+	}
+    }
+
+    verify_apphub_hrl_target::<WebAppPackageVersionEntry,_>(
+	&app_version.apphub_hrl, &app_version.apphub_hrl_hash, "get_webapp_package_version_entry"
+    )?;
+
+    let verify = input.verify.unwrap_or(false);
+
+    if verify {
+	let (verified, calculated, manifest) = verify_bundle_hashes( &app_version )?;
+
+	if !verified {
+	    return Err( AppStoreError::BundleHashMismatch {
+		expected: format!("{:?}", app_version.bundle_hashes ),
+		received: format!("{:?}", calculated ),
+	    }.into() );
+	}
+
+	app_version.manifest = Some( manifest );
+    }
+
     let entity = create_entity( &app_version )?;
 
+    if verify {
+	create_bundle_verification( &entity, true )?;
+    }
+
     { // Link from App
 	entity.link_from( &input.for_app, LinkTypes::AppToAppVersion, None )?;
     }
+    { // Path via DNAs
+	index_app_version_dnas( &entity )?;
+    }
+    { // Path via Change Log
+	record_change( ChangeSubjectType::AppVersion, ChangeType::Create, &entity.id, &entity.action )?;
+    }
 
     Ok( entity )
 }
 
 
+/// Link each DNA of an App Version to it (skipping DNAs that are already linked)
+pub(crate) fn index_app_version_dnas(entity: &Entity<AppVersionEntry>) -> ExternResult<()> {
+    for dna_hash in entity.content.all_dna_hashes() {
+	let base = dna_link_base( &dna_hash );
+	let already_linked = get_links(
+	    GetLinksInputBuilder::try_new( base.clone(), LinkTypes::DnaToAppVersion )?.build()
+	)?.into_iter()
+	    .any( |link| link.target == entity.id.clone().into() );
+
+	if !already_linked {
+	    entity.link_from( &base, LinkTypes::DnaToAppVersion, None )?;
+	}
+    }
+
+    Ok(())
+}
+
+
+#[hdk_extern]
+pub fn get_app_versions_for_dna(dna_hash: DnaHash) -> ExternResult<Vec<DnaAppVersion>> {
+    debug!("Get app versions for DNA: {}", dna_hash );
+    let app_versions : Vec<Entity<AppVersionEntry>> = get_entities(
+	&dna_link_base( &dna_hash ),
+	LinkTypes::DnaToAppVersion,
+	None
+    )?;
+    let mut results = vec![];
+
+    for app_version in app_versions {
+	// Skip listings that cannot be shown (eg. removed by the governance group)
+	if let Ok(app) = get_app( GetEntityInput {
+	    id: app_version.content.for_app.clone(),
+	}) {
+	    results.push( DnaAppVersion {
+		app,
+		app_version,
+	    });
+	}
+    }
+
+    Ok( results )
+}
+
+
 #[hdk_extern]
 pub fn get_app_version(input: GetEntityInput) -> ExternResult<Entity<AppVersionEntry>> {
     debug!("Get app_version: {}", input.id );
@@ -81,15 +188,6 @@ pub fn get_app_version(input: GetEntityInput) -> ExternResult<Entity<AppVersionE
 }
 
 
-#[derive(Debug, Deserialize, Clone)]
-pub struct UpdateProperties {
-    pub version: Option<String>,
-    pub published_at: Option<u64>,
-    pub last_updated: Option<u64>,
-    pub metadata: Option<BTreeMap<String, RmpvValue>>,
-}
-pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
-
 #[hdk_extern]
 pub fn update_app_version(input: UpdateInput) -> ExternResult<Entity<AppVersionEntry>> {
     debug!("Updating AppVersion: {}", input.base );
@@ -108,22 +206,34 @@ pub fn update_app_version(input: UpdateInput) -> ExternResult<Entity<AppVersionE
 	    current.metadata = props.metadata
 		.unwrap_or( current.metadata );
 
+	    // Delegates need a delegation that covers this update
+	    let app : Entity<AppEntry> = get_entity( &current.for_app )?;
+
+	    if !app.content.editors.contains( &current.author ) {
+		current.delegation = find_publishing_delegation(
+		    &current.for_app,
+		    &current.author,
+		    now()?,
+		)?.or( current.delegation );
+	    }
+
+	    current.upgrade();
+
 	    Ok( current )
 	})?;
 
+    record_change( ChangeSubjectType::AppVersion, ChangeType::Update, &entity.id, &entity.action )?;
+
     Ok( entity )
 }
 
 
-#[derive(Debug, Deserialize)]
-pub struct DeleteInput {
-    pub base: ActionHash,
-}
-
 #[hdk_extern]
 pub fn delete_app_version(input: DeleteInput) -> ExternResult<ActionHash> {
     debug!("Deleting AppVersion: {}", input.base );
     let delete_hash = delete_entity::<AppVersionEntry, EntryTypes>( &input.base )?;
 
+    record_change( ChangeSubjectType::AppVersion, ChangeType::Delete, &input.base, &delete_hash )?;
+
     Ok( delete_hash )
 }
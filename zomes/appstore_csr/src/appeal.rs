@@ -0,0 +1,205 @@
+use crate::{
+    hdk,
+    get_subject_moderator_actions,
+    update_subject_moderated_state,
+};
+
+use std::collections::BTreeMap;
+use hdk::prelude::*;
+use hdk_extensions::{
+    agent_id,
+    follow_evolutions,
+};
+use appstore::{
+    LinkTypes,
+    RmpvValue,
+    GroupAnchorEntry,
+    AppStoreError,
+    ModeratorActionEntry,
+    ModerationSubjectType,
+    AppealEntry,
+    AppealResolutionEntry,
+    get_moderation_subject,
+
+    hc_crud::{
+        now, create_entity, get_entity, get_entities,
+        Entity,
+    },
+    hdi_extensions::{
+        AnyLinkableHashTransformer,
+    },
+};
+pub use appstore_sdk::{
+    CreateAppealInput as CreateInput,
+    ResolveAppealInput as ResolveInput,
+};
+
+
+/// Determine whether a moderated subject is an App or a Publisher
+fn get_subject_type(subject_id: &ActionHash) -> ExternResult<ModerationSubjectType> {
+    let (subject_type, _) = get_moderation_subject( subject_id )?
+        .ok_or( AppStoreError::InvalidModerationSubject {
+            subject: subject_id.to_owned(),
+        })?;
+
+    Ok( subject_type )
+}
+
+
+/// Appeal a moderator action as one of the moderated App's (or Publisher's) editors
+#[hdk_extern]
+pub fn create_appeal(input: CreateInput) -> ExternResult<Entity<AppealEntry>> {
+    debug!("Creating Appeal for moderator action: {}", input.moderator_action );
+    let moderator_action : Entity<ModeratorActionEntry> = get_entity( &input.moderator_action )?;
+
+    let appeal = AppealEntry {
+	subject_id: moderator_action.content.subject_id.clone(),
+	moderator_action: input.moderator_action.clone(),
+	group_id: moderator_action.content.group_id.0.clone(),
+	author: agent_id()?,
+	published_at: now()?,
+	message: input.message,
+	metadata: input.metadata
+	    .unwrap_or( BTreeMap::new() ),
+    };
+    let entity = create_entity( &appeal )?;
+
+    { // Path via Moderator Action's Appeals
+	entity.link_from(
+            &input.moderator_action,
+            LinkTypes::ModeratorActionToAppeal,
+            None
+        )?;
+    }
+    { // Path via Group's Appeals
+	let group_anchor_hash = hash_entry( &GroupAnchorEntry {
+	    group_id: appeal.group_id.clone(),
+	})?;
+	entity.link_from(
+            &group_anchor_hash,
+            LinkTypes::GroupAnchorToAppeal,
+            Some( format!(
+                "{}::{}",
+                get_subject_type( &appeal.subject_id )?.tag_prefix(),
+                appeal.subject_id,
+            ).into_bytes() )
+        )?;
+    }
+
+    Ok( entity )
+}
+
+
+/// Get Appeals made against the given moderator action
+#[hdk_extern]
+pub fn get_appeals_for_moderator_action(moderator_action: ActionHash) -> ExternResult<Vec<Entity<AppealEntry>>> {
+    let collection = get_entities(
+        &moderator_action,
+        LinkTypes::ModeratorActionToAppeal,
+        None
+    )?;
+
+    Ok( collection )
+}
+
+
+/// Get the resolution for the given Appeal ID (if it has been resolved)
+#[hdk_extern]
+pub fn get_appeal_resolution(appeal_id: ActionHash) -> ExternResult<Option<Entity<AppealResolutionEntry>>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            appeal_id,
+            LinkTypes::AppealToAppealResolution,
+        )?.build()
+    )?;
+
+    Ok( match links.iter().min_by_key( |link| link.timestamp ) {
+        Some(link) => Some( get_entity( &link.target.clone().must_be_action_hash()? )? ),
+        None => None,
+    })
+}
+
+
+/// Get all Appeals for the given Group ID that have not been resolved
+#[hdk_extern]
+pub fn get_open_appeals(group_id: ActionHash) -> ExternResult<Vec<Entity<AppealEntry>>> {
+    let group_anchor_hash = hash_entry( &GroupAnchorEntry {
+        group_id: group_id,
+    })?;
+    let appeals : Vec<Entity<AppealEntry>> = get_entities(
+        &group_anchor_hash,
+        LinkTypes::GroupAnchorToAppeal,
+        None
+    )?;
+
+    let mut collection = vec![];
+
+    for appeal in appeals {
+        if get_appeal_resolution( appeal.id.clone() )?.is_none() {
+            collection.push( appeal );
+        }
+    }
+
+    Ok( collection )
+}
+
+
+/// Resolve an Appeal as a member of the moderating Group, optionally restoring the subject
+#[hdk_extern]
+pub fn resolve_appeal(input: ResolveInput) -> ExternResult<Entity<AppealResolutionEntry>> {
+    debug!("Resolving Appeal: {}", input.appeal );
+    let appeal : Entity<AppealEntry> = get_entity( &input.appeal )?;
+    let group_id = appeal.content.group_id.clone();
+
+    let restore_action = match input.restore.unwrap_or(false) {
+        true => {
+            let subject_id = appeal.content.subject_id.clone();
+            let subject_type = get_subject_type( &subject_id )?.tag_prefix();
+            let mut metadata = get_subject_moderator_actions(
+                &group_id,
+                format!("{}::{}", subject_type, subject_id ),
+            )?
+                .last()
+                .map( |state| state.content.metadata.clone() )
+                .unwrap_or( BTreeMap::new() );
+            metadata.insert( "remove".to_string(), RmpvValue::Boolean(false) );
+
+            let moderator_action = update_subject_moderated_state(
+                group_id.clone(),
+                subject_type,
+                subject_id,
+                input.message.clone(),
+                metadata,
+            )?;
+
+            Some( moderator_action.action )
+        },
+        false => None,
+    };
+
+    let group_rev = follow_evolutions( &group_id )?.last().cloned()
+        .ok_or( AppStoreError::MissingGroupRevision {
+            group: group_id.clone(),
+        })?;
+    let resolution = AppealResolutionEntry {
+	appeal: input.appeal.clone(),
+	group_id: (group_id, group_rev),
+	author: agent_id()?,
+	published_at: now()?,
+	message: input.message,
+	metadata: input.metadata
+	    .unwrap_or( BTreeMap::new() ),
+	restore_action: restore_action,
+    };
+    let entity = create_entity( &resolution )?;
+
+    { // Path via Appeal's Resolution
+	entity.link_from(
+            &input.appeal,
+            LinkTypes::AppealToAppealResolution,
+            None
+        )?;
+    }
+
+    Ok( entity )
+}
@@ -0,0 +1,650 @@
+use crate::{
+    hdk,
+};
+
+use std::fmt::Debug;
+use std::collections::HashSet;
+use hdk::prelude::*;
+use hdk_extensions::{
+    agent_id,
+};
+use appstore::{
+    LinkTypes,
+    AppStoreError,
+    rmpv,
+    RmpvValue,
+    HRL,
+    AppVersionEntry,
+    BundleHashes,
+    BundleVerificationEntry,
+    ManifestSummary,
+    SourceOwnershipClaim,
+    SourceOwnershipProof,
+
+    appstore_types::{
+        bundle::summarize_webhapp,
+    },
+    hc_crud::{
+        now, create_entity, get_entity, get_entities,
+        Entity,
+        GetEntityInput,
+    },
+};
+pub use appstore_sdk::{
+    GetDownloadManifestInput,
+    DownloadResourceType,
+    DownloadResource,
+    DownloadBlock,
+    DownloadMemory,
+    DownloadManifest,
+    GetDeltaInput,
+    MemoryDelta,
+    AppVersionDelta,
+};
+use apphub_sdk::{
+    apphub_types::{
+        Authority,
+        AppEntry as AppHubAppEntry,
+        UiEntry,
+        WebAppEntry,
+        WebAppPackageEntry,
+        WebAppPackageVersionEntry,
+        mere_memory_types::{
+            MemoryEntry,
+            MemoryBlockEntry,
+        },
+    },
+    dnahub_types::{
+        DnaEntry,
+    },
+    zomehub_types::{
+        ZomeEntry,
+    },
+};
+use coop_content_sdk::{
+    call_local_zome_decode,
+};
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteCallInput {
+    pub dna: DnaHash,
+    pub zome: String,
+    pub function: String,
+    pub payload: RmpvValue,
+}
+
+/// Call a zome function on a DevHub host through the Portal cell in this hApp
+pub(crate) fn portal_remote_call<T, R>(
+    dna: &DnaHash,
+    zome: &str,
+    function: &str,
+    payload: T,
+) -> ExternResult<R>
+where
+    T: Serialize + Debug,
+    R: serde::de::DeserializeOwned + Debug,
+{
+    let payload = rmpv::ext::to_value( &payload )
+        .map_err(|err| AppStoreError::SerializationFailed {
+            context: "serialize remote call payload".to_string(),
+            reason: err.to_string(),
+        })?;
+
+    let response = call(
+        CallTargetCell::OtherRole( "portal".into() ),
+        "portal_csr",
+        "remote_call".into(),
+        None,
+        RemoteCallInput {
+            dna: dna.to_owned(),
+            zome: zome.to_string(),
+            function: function.to_string(),
+            payload: payload,
+        },
+    )?;
+
+    match response {
+        ZomeCallResponse::Ok(extern_io) => extern_io.decode()
+            .map_err(|err| AppStoreError::SerializationFailed {
+                context: format!("decode remote call response for {}::{}", zome, function ),
+                reason: format!("{:?}", err ),
+            }.into() ),
+        other => Err( AppStoreError::RemoteCallFailed {
+            dna: dna.to_owned(),
+            zome: zome.to_string(),
+            function: function.to_string(),
+            reason: format!("{:?}", other ),
+        }.into() ),
+    }
+}
+
+
+/// Fetch an entry from a DevHub host and check it against the expected [`EntryHash`]
+pub(crate) fn get_apphub_entry<T, E>(
+    dna: &DnaHash,
+    zome: &str,
+    function: &str,
+    target: &AnyDhtHash,
+    expected_hash: &EntryHash,
+) -> ExternResult<T>
+where
+    T: serde::de::DeserializeOwned + Debug + Clone,
+    Entry: TryFrom<T, Error = E>,
+    WasmError: From<E>,
+{
+    let entry : T = portal_remote_call( dna, zome, function, target )?;
+    let received_hash = hash_entry( entry.clone() )?;
+
+    if received_hash != *expected_hash {
+        return Err( AppStoreError::EntryHashMismatch {
+            expected: expected_hash.to_owned(),
+            received: received_hash,
+        }.into() );
+    }
+
+    Ok( entry )
+}
+
+
+/// Calculate the mere_memory hash (hex encoded SHA-256) for the given bytes
+pub(crate) fn calculate_hash(bytes: Vec<u8>) -> ExternResult<String> {
+    call_local_zome_decode!(
+        String,
+        "mere_memory_api",
+        "calculate_hash",
+        SerializedBytes::from( UnsafeBytes::from( bytes ) )
+    )
+}
+
+
+/// Fetch the DevHub webapp package version and webapp entries for an App Version
+pub(crate) fn get_app_version_webapp(
+    app_version: &AppVersionEntry,
+) -> ExternResult<(WebAppPackageVersionEntry, WebAppEntry)> {
+    let dna = &app_version.apphub_hrl.dna;
+
+    let webapp_version : WebAppPackageVersionEntry = get_apphub_entry(
+        dna,
+        "apphub_csr",
+        "get_webapp_package_version_entry",
+        &app_version.apphub_hrl.target,
+        &app_version.apphub_hrl_hash,
+    )?;
+    let webapp : WebAppEntry = get_apphub_entry(
+        dna,
+        "apphub_csr",
+        "get_webapp_entry",
+        &webapp_version.webapp.clone().into(),
+        &webapp_version.webapp,
+    )?;
+
+    Ok( (webapp_version, webapp) )
+}
+
+
+/// Fetch the mere_memory entry (and its address) holding the UI of a DevHub webapp
+pub(crate) fn get_webapp_ui_memory(
+    dna: &DnaHash,
+    webapp: &WebAppEntry,
+) -> ExternResult<(EntryHash, MemoryEntry)> {
+    let ui_addr = webapp.resources.get( &webapp.manifest.ui.bundled )
+        .ok_or( AppStoreError::MissingWebAppResource {
+            path: webapp.manifest.ui.bundled.clone(),
+        })?;
+    let ui : UiEntry = get_apphub_entry(
+        dna,
+        "apphub_csr",
+        "get_ui_entry",
+        &ui_addr.clone().into(),
+        ui_addr,
+    )?;
+    let ui_memory : MemoryEntry = get_apphub_entry(
+        dna,
+        "mere_memory_api",
+        "get_memory_entry",
+        &ui.mere_memory_addr.clone().into(),
+        &ui.mere_memory_addr,
+    )?;
+
+    Ok( (ui.mere_memory_addr, ui_memory) )
+}
+
+
+/// Recompute the bundle hashes of an App Version from its DevHub webapp package version and
+/// summarize the bundle's manifests
+///
+/// Returns whether every hash matched along with the calculated hashes.
+pub(crate) fn verify_bundle_hashes(
+    app_version: &AppVersionEntry,
+) -> ExternResult<(bool, BundleHashes, ManifestSummary)> {
+    let dna = &app_version.apphub_hrl.dna;
+    let (webapp_version, webapp) = get_app_version_webapp( app_version )?;
+    let (_, ui_memory) = get_webapp_ui_memory( dna, &webapp )?;
+
+    let bundle : SerializedBytes = portal_remote_call(
+        dna,
+        "apphub_csr",
+        "get_webhapp_bundle",
+        webapp_version.webapp.clone(),
+    )?;
+    let calc_hash = calculate_hash( bundle.bytes().to_owned() )?;
+    let manifest = summarize_webhapp( bundle.bytes() )
+        .map_err(|err| AppStoreError::InvalidWebhappBundle {
+            reason: err.to_string(),
+        })?;
+
+    debug!(
+        "Bundle hashes: expected {:?}; calculated (hash: {}, ui_hash: {}, happ_hash: {})",
+        app_version.bundle_hashes, calc_hash, manifest.ui_hash, manifest.happ_hash,
+    );
+
+    let calculated = BundleHashes {
+        hash: calc_hash,
+        ui_hash: ui_memory.hash,
+        happ_hash: manifest.happ_hash.clone(),
+    };
+    let verified = calculated == app_version.bundle_hashes
+        && manifest.ui_hash == app_version.bundle_hashes.ui_hash;
+
+    Ok( (verified, calculated, manifest) )
+}
+
+
+/// Record this agent's [`BundleVerificationEntry`] for an App Version
+pub(crate) fn create_bundle_verification(
+    app_version: &Entity<AppVersionEntry>,
+    verified: bool,
+) -> ExternResult<Entity<BundleVerificationEntry>> {
+    let entity = create_entity( &BundleVerificationEntry {
+        app_version: app_version.id.clone(),
+        bundle_hashes: app_version.content.bundle_hashes.clone(),
+        verified,
+        author: agent_id()?,
+        published_at: now()?,
+    })?;
+
+    entity.link_from( &app_version.id, LinkTypes::AppVersionToBundleVerification, None )?;
+
+    Ok( entity )
+}
+
+
+/// Summarize the manifests of a webhapp bundle without publishing anything
+#[hdk_extern]
+pub fn inspect_webhapp_bundle(bundle: SerializedBytes) -> ExternResult<ManifestSummary> {
+    summarize_webhapp( bundle.bytes() )
+        .map_err(|err| AppStoreError::InvalidWebhappBundle {
+            reason: err.to_string(),
+        }.into() )
+}
+
+
+/// Verify the bundle hashes of an App Version against its DevHub package and record this agent's
+/// attestation
+#[hdk_extern]
+pub fn verify_app_version(input: GetEntityInput) -> ExternResult<Entity<BundleVerificationEntry>> {
+    debug!("Verifying AppVersion bundle hashes: {}", input.id );
+    let app_version : Entity<AppVersionEntry> = get_entity( &input.id )?;
+    let (verified, _, _) = verify_bundle_hashes( &app_version.content )?;
+
+    create_bundle_verification( &app_version, verified )
+}
+
+
+/// Get every agent's bundle verification for an App Version
+#[hdk_extern]
+pub fn get_bundle_verifications(input: GetEntityInput) -> ExternResult<Vec<Entity<BundleVerificationEntry>>> {
+    get_entities(
+        &input.id,
+        LinkTypes::AppVersionToBundleVerification,
+        None
+    )
+}
+
+
+/// Check that an action HRL target created the entry at `apphub_hrl_hash`
+///
+/// Validation cannot read records from the DevHub DNA, so an action target is resolved through
+/// the Portal when it is published.  Entry targets are checked by [`appstore::validate_apphub_hrl`].
+pub(crate) fn verify_apphub_hrl_target<T, E>(
+    apphub_hrl: &HRL,
+    apphub_hrl_hash: &EntryHash,
+    function: &str,
+) -> ExternResult<()>
+where
+    T: serde::de::DeserializeOwned + Debug + Clone,
+    Entry: TryFrom<T, Error = E>,
+    WasmError: From<E>,
+{
+    if apphub_hrl.target.clone().into_action_hash().is_some() {
+        get_apphub_entry::<T, E>(
+            &apphub_hrl.dna,
+            "apphub_csr",
+            function,
+            &apphub_hrl.target,
+            apphub_hrl_hash,
+        )?;
+    }
+
+    Ok(())
+}
+
+
+/// Check through the Portal that the given agent maintains the DevHub webapp package
+///
+/// Returns the package entry as a msgpack value for [`SourceOwnershipProof::package`].
+pub(crate) fn verify_package_maintainer(
+    apphub_hrl: &HRL,
+    apphub_hrl_hash: &EntryHash,
+    agent: &AgentPubKey,
+) -> ExternResult<RmpvValue> {
+    let package : WebAppPackageEntry = get_apphub_entry(
+        &apphub_hrl.dna,
+        "apphub_csr",
+        "get_webapp_package_entry",
+        &apphub_hrl.target,
+        apphub_hrl_hash,
+    )?;
+
+    match &package.maintainer {
+        Authority::Agent(maintainer) if maintainer == agent => (),
+        other => return Err( AppStoreError::NotPackageMaintainer {
+            signer: agent.to_owned(),
+            maintainer: match other {
+                Authority::Agent(maintainer) => Some( maintainer.to_owned() ),
+                _ => None,
+            },
+        }.into() ),
+    }
+
+    // Same bytes that DevHub hashed for 'apphub_hrl_hash'
+    let bytes = encode( &package )
+        .map_err(|err| AppStoreError::SerializationFailed {
+            context: "encode DevHub package entry".to_string(),
+            reason: format!("{:?}", err ),
+        })?;
+
+    rmpv::decode::read_value( &mut bytes.as_slice() )
+        .map_err(|err| AppStoreError::SerializationFailed {
+            context: "decode DevHub package entry".to_string(),
+            reason: err.to_string(),
+        }.into() )
+}
+
+
+/// Sign a source ownership claim as the maintainer of its DevHub webapp package
+#[hdk_extern]
+pub fn sign_source_ownership_claim(claim: SourceOwnershipClaim) -> ExternResult<SourceOwnershipProof> {
+    let signer = agent_id()?;
+    let package = verify_package_maintainer( &claim.apphub_hrl, &claim.apphub_hrl_hash, &signer )?;
+
+    Ok( SourceOwnershipProof {
+        signature: sign( signer.clone(), &claim )?,
+        signer,
+        package,
+    })
+}
+
+
+/// Fetch the size of a mere_memory block from a DevHub host
+pub(crate) fn get_download_block(
+    dna: &DnaHash,
+    address: EntryHash,
+) -> ExternResult<DownloadBlock> {
+    let block : MemoryBlockEntry = get_apphub_entry(
+        dna,
+        "mere_memory_api",
+        "get_memory_block_entry",
+        &address.clone().into(),
+        &address,
+    )?;
+
+    Ok( DownloadBlock {
+        address,
+        size: block.bytes.len() as u64,
+    })
+}
+
+
+/// Fetch a mere_memory entry from a DevHub host
+///
+/// Block sizes are only fetched when `with_sizes` is set; otherwise they are `0`.
+pub(crate) fn get_download_memory(
+    dna: &DnaHash,
+    address: &EntryHash,
+    with_sizes: bool,
+) -> ExternResult<DownloadMemory> {
+    let memory : MemoryEntry = get_apphub_entry(
+        dna,
+        "mere_memory_api",
+        "get_memory_entry",
+        &address.clone().into(),
+        address,
+    )?;
+    let mut blocks = vec![];
+
+    for block_addr in memory.block_addresses {
+        blocks.push( match with_sizes {
+            true => get_download_block( dna, block_addr )?,
+            false => DownloadBlock {
+                address: block_addr,
+                size: 0,
+            },
+        });
+    }
+
+    Ok( DownloadMemory {
+        address: address.to_owned(),
+        hash: memory.hash,
+        compression: memory.compression,
+        uncompressed_size: memory.uncompressed_size,
+        memory_size: memory.memory_size,
+        blocks,
+    })
+}
+
+
+/// Resolve an App Version into every DevHub entry, memory and block that makes up its bundle
+///
+/// The webapp's UI and hApp are in the AppHub DNA, the hApp's DNAs in the DnaHub DNA and the
+/// DNAs' zomes (and their memories) in the ZomeHub DNA.  Every entry is checked against the
+/// address it was fetched by.
+pub(crate) fn get_download_manifest(
+    entity: &Entity<AppVersionEntry>,
+    dnahub_dna: &DnaHash,
+    zomehub_dna: &DnaHash,
+    with_sizes: bool,
+) -> ExternResult<DownloadManifest> {
+    let app_version = &entity.content;
+    let dna = &app_version.apphub_hrl.dna;
+    let (webapp_version, webapp) = get_app_version_webapp( app_version )?;
+    let ui_path = webapp.manifest.ui.bundled.clone();
+    let happ_path = webapp.manifest.happ_manifest.bundled.clone();
+    let mut resources = vec![];
+
+    let ui_addr = webapp.resources.get( &ui_path )
+        .ok_or( AppStoreError::MissingWebAppResource {
+            path: ui_path.clone(),
+        })?;
+    let ui : UiEntry = get_apphub_entry(
+        dna,
+        "apphub_csr",
+        "get_ui_entry",
+        &ui_addr.clone().into(),
+        ui_addr,
+    )?;
+
+    resources.push( DownloadResource {
+        path: ui_path.clone(),
+        resource_type: DownloadResourceType::Ui,
+        address: ui_addr.to_owned(),
+        memory: Some( get_download_memory( dna, &ui.mere_memory_addr, with_sizes )? ),
+    });
+
+    let happ_addr = webapp.resources.get( &happ_path )
+        .ok_or( AppStoreError::MissingWebAppResource {
+            path: happ_path.clone(),
+        })?;
+    let happ : AppHubAppEntry = get_apphub_entry(
+        dna,
+        "apphub_csr",
+        "get_app_entry",
+        &happ_addr.clone().into(),
+        happ_addr,
+    )?;
+
+    resources.push( DownloadResource {
+        path: happ_path.clone(),
+        resource_type: DownloadResourceType::Happ,
+        address: happ_addr.to_owned(),
+        memory: None,
+    });
+
+    for (dna_path, dna_addr) in happ.resources.iter() {
+        let dna_entry : DnaEntry = get_apphub_entry(
+            dnahub_dna,
+            "dnahub_csr",
+            "get_dna_entry",
+            &dna_addr.clone().into(),
+            dna_addr,
+        )?;
+        let dna_path = format!("{}/{}", happ_path, dna_path );
+
+        resources.push( DownloadResource {
+            path: dna_path.clone(),
+            resource_type: DownloadResourceType::Dna,
+            address: dna_addr.to_owned(),
+            memory: None,
+        });
+
+        for (zome_path, zome_addr) in dna_entry.resources.iter() {
+            let zome : ZomeEntry = get_apphub_entry(
+                zomehub_dna,
+                "zomehub_csr",
+                "get_zome_entry",
+                &zome_addr.clone().into(),
+                zome_addr,
+            )?;
+
+            resources.push( DownloadResource {
+                path: format!("{}/{}", dna_path, zome_path ),
+                resource_type: DownloadResourceType::Zome,
+                address: zome_addr.to_owned(),
+                memory: Some( get_download_memory( zomehub_dna, &zome.mere_memory_addr, with_sizes )? ),
+            });
+        }
+    }
+
+    Ok( DownloadManifest {
+        app_version: entity.id.clone(),
+        host_dna: dna.to_owned(),
+        dnahub_dna: dnahub_dna.to_owned(),
+        zomehub_dna: zomehub_dna.to_owned(),
+        webapp_package_version: app_version.apphub_hrl.target.clone(),
+        webapp: webapp_version.webapp.clone(),
+        bundle_hashes: app_version.bundle_hashes.clone(),
+        resources,
+    })
+}
+
+
+/// Get the download manifest for an App Version
+#[hdk_extern]
+pub fn get_app_version_download_manifest(input: GetDownloadManifestInput) -> ExternResult<DownloadManifest> {
+    debug!("Get download manifest for AppVersion: {}", input.id );
+    let entity : Entity<AppVersionEntry> = get_entity( &input.id )?;
+
+    get_download_manifest( &entity, &input.dnahub_dna, &input.zomehub_dna, true )
+}
+
+
+/// Compare every resource and mere_memory block of two App Versions of the same App
+///
+/// Block addresses are the [`EntryHash`] of each `MemoryBlockEntry` (see
+/// `hash_mere_memory_block_entry`), so an address present in both versions is an identical block.
+#[hdk_extern]
+pub fn get_app_version_delta(input: GetDeltaInput) -> ExternResult<AppVersionDelta> {
+    debug!("Get delta for AppVersion: {} => {}", input.from, input.to );
+    let from : Entity<AppVersionEntry> = get_entity( &input.from )?;
+    let to : Entity<AppVersionEntry> = get_entity( &input.to )?;
+
+    if from.content.for_app != to.content.for_app {
+        return Err( AppStoreError::AppVersionAppMismatch {
+            from_app: from.content.for_app,
+            to_app: to.content.for_app,
+        }.into() );
+    }
+
+    // Sizes are only fetched for the blocks that have to be downloaded
+    let from_manifest = get_download_manifest( &from, &input.dnahub_dna, &input.zomehub_dna, false )?;
+    let to_manifest = get_download_manifest( &to, &input.dnahub_dna, &input.zomehub_dna, false )?;
+    let from_addresses : HashSet<EntryHash> = from_manifest.resources.iter()
+        .map( |resource| resource.address.clone() )
+        .collect();
+    let from_blocks : HashSet<EntryHash> = from_manifest.resources.iter()
+        .filter_map( |resource| resource.memory.as_ref() )
+        .flat_map( |memory| memory.blocks.iter().map( |block| block.address.clone() ) )
+        .collect();
+
+    let mut changed_resources = vec![];
+    let mut memories = vec![];
+    let mut fetched_blocks = HashSet::new();
+    let mut download_size = 0;
+
+    for resource in to_manifest.resources {
+        if !from_addresses.contains( &resource.address ) {
+            changed_resources.push( DownloadResource {
+                memory: None,
+                ..resource.clone()
+            });
+        }
+
+        let Some(memory) = resource.memory else {
+            continue;
+        };
+        let memory_dna = match resource.resource_type {
+            DownloadResourceType::Zome => &input.zomehub_dna,
+            _ => &to_manifest.host_dna,
+        };
+        let mut unchanged_blocks = vec![];
+        let mut changed_blocks = vec![];
+
+        for block in memory.blocks {
+            if from_blocks.contains( &block.address ) {
+                unchanged_blocks.push( block.address );
+                continue;
+            }
+
+            let block = get_download_block( memory_dna, block.address )?;
+
+            // The same block can appear in more than one memory but only has to be fetched once
+            if fetched_blocks.insert( block.address.clone() ) {
+                download_size += block.size;
+            }
+
+            changed_blocks.push( block );
+        }
+
+        memories.push( MemoryDelta {
+            path: resource.path,
+            resource_type: resource.resource_type,
+            address: memory.address,
+            hash: memory.hash,
+            unchanged_blocks,
+            changed_blocks,
+        });
+    }
+
+    Ok( AppVersionDelta {
+        from: from.id,
+        to: to.id,
+        host_dna: to_manifest.host_dna,
+        dnahub_dna: input.dnahub_dna,
+        zomehub_dna: input.zomehub_dna,
+        changed_resources,
+        memories,
+        download_size,
+    })
+}
+
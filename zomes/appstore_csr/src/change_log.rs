@@ -0,0 +1,130 @@
+use crate::{
+    hdk,
+};
+
+use hdk::prelude::*;
+use hdk_extensions::{
+    must_get,
+};
+use appstore::{
+    LinkTypes,
+    ChangeSubjectType,
+    ChangeType,
+    ChangeLogTag,
+    ChangeLogItem,
+    CHANGE_LOG_BUCKET_MS,
+    change_log_anchor,
+    change_log_tag,
+    parse_change_log_tag,
+
+    hc_crud::{
+        now,
+    },
+    hdi_extensions::{
+        AnyLinkableHashTransformer,
+    },
+};
+pub use appstore_sdk::{
+    GetChangesSinceInput,
+    ChangeLogPage,
+};
+
+
+const DEFAULT_PAGE_LIMIT : usize = 100;
+
+
+/// Link the action that changed an entity from the change-log bucket of that action's time
+pub(crate) fn record_change(
+    subject_type: ChangeSubjectType,
+    change_type: ChangeType,
+    subject_id: &ActionHash,
+    action: &ActionHash,
+) -> ExternResult<ActionHash> {
+    let timestamp = must_get( action )?.action().timestamp().as_micros() as u64 / 1_000;
+
+    create_link(
+        change_log_anchor( timestamp )?,
+        action.to_owned(),
+        LinkTypes::ChangeLogToAction,
+        change_log_tag( ChangeLogTag {
+            subject_type,
+            change_type,
+            subject_id: subject_id.to_owned(),
+            timestamp,
+        })?,
+    )
+}
+
+
+fn get_change_log_bucket(bucket: u64) -> ExternResult<Vec<ChangeLogItem>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            change_log_anchor( bucket * CHANGE_LOG_BUCKET_MS )?,
+            LinkTypes::ChangeLogToAction,
+        )?.build()
+    )?;
+
+    let mut items = vec![];
+
+    for link in links {
+        let tag = parse_change_log_tag( &link.tag )?;
+
+        items.push( ChangeLogItem {
+            subject_type: tag.subject_type,
+            change_type: tag.change_type,
+            subject_id: tag.subject_id,
+            action: link.target.must_be_action_hash()?,
+            author: link.author,
+            timestamp: tag.timestamp,
+        });
+    }
+
+    items.sort_by(|a, b| {
+        a.timestamp.cmp( &b.timestamp )
+            .then_with( || a.action.cmp( &b.action ) )
+    });
+
+    Ok( items )
+}
+
+
+/// Get the Publisher, App and App Version changes made at or after the given time
+///
+/// Changes are ordered by time then action hash.  To get the next page, pass the `timestamp`
+/// and `action` of the last change as `since` and `after`.
+#[hdk_extern]
+pub fn get_changes_since(input: GetChangesSinceInput) -> ExternResult<ChangeLogPage> {
+    debug!("Get changes since: {} (after {:?})", input.since, input.after );
+    let limit = input.limit
+        .map( |limit| limit as usize )
+        .unwrap_or( DEFAULT_PAGE_LIMIT );
+    let origin_time = dna_info()?.modifiers.origin_time.as_micros() as u64 / 1_000;
+    let first_bucket = std::cmp::max( input.since, origin_time ) / CHANGE_LOG_BUCKET_MS;
+    let last_bucket = now()? / CHANGE_LOG_BUCKET_MS;
+
+    let mut changes = vec![];
+
+    for bucket in first_bucket ..= last_bucket {
+        changes.extend(
+            get_change_log_bucket( bucket )?.into_iter()
+                .filter(|item| match &input.after {
+                    Some(after) => item.timestamp > input.since
+                        || ( item.timestamp == input.since && &item.action > after ),
+                    None => item.timestamp >= input.since,
+                })
+        );
+
+        // Buckets are in time order so a full page cannot gain earlier items
+        if changes.len() > limit {
+            break;
+        }
+    }
+
+    let more = changes.len() > limit;
+    changes.truncate( limit );
+
+    Ok( ChangeLogPage {
+        changes,
+        more,
+    })
+}
@@ -0,0 +1,127 @@
+use crate::{
+    hdk,
+    GetForAppInput,
+};
+
+use std::collections::BTreeMap;
+use hdk::prelude::*;
+use hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use appstore::{
+    EntryTypes,
+    LinkTypes,
+    EntityId,
+    PublishingDelegationEntry,
+    MAX_DELEGATION_LIFETIME,
+
+    hc_crud::{
+        now, create_entity, get_entity, get_entities, delete_entity,
+        Entity,
+        GetEntityInput,
+    },
+};
+pub use appstore_sdk::{
+    IssuePublishingDelegationInput as IssueInput,
+};
+
+
+/// Get the time (in ms) of the action that issued a delegation
+pub(crate) fn get_delegation_issued_at(id: &ActionHash) -> ExternResult<u64> {
+    let record = must_get( id )?;
+
+    Ok( record.action().timestamp().as_micros() as u64 / 1_000 )
+}
+
+
+/// Find a delegation allowing the given agent to write a version of the App at the given time
+pub(crate) fn find_publishing_delegation(
+    for_app: &EntityId,
+    agent: &AgentPubKey,
+    at: u64,
+) -> ExternResult<Option<ActionHash>> {
+    let delegations = get_publishing_delegations( GetForAppInput {
+        for_app: for_app.to_owned(),
+    })?;
+
+    for entity in delegations {
+        let issued_at = get_delegation_issued_at( &entity.id )?;
+
+        if entity.content.covers( for_app, agent, issued_at, at ) {
+            return Ok( Some( entity.id ) );
+        }
+    }
+
+    Ok( None )
+}
+
+
+/// Allow another agent (eg. a CI bot) to publish versions of an App that this agent edits
+///
+/// Delegations must expire; `expires_at` defaults to (and cannot exceed)
+/// [`MAX_DELEGATION_LIFETIME`] from now.
+#[hdk_extern]
+pub fn issue_publishing_delegation(input: IssueInput) -> ExternResult<Entity<PublishingDelegationEntry>> {
+    debug!("Issuing publishing delegation for App ({}) to: {}", input.for_app, input.delegate );
+    let default_now = now()?;
+    let delegation = PublishingDelegationEntry {
+	for_app: input.for_app.clone(),
+	delegate: input.delegate,
+
+	author: agent_id()?,
+	published_at: default_now,
+	last_updated: default_now,
+	metadata: input.metadata
+	    .unwrap_or( BTreeMap::new() ),
+
+	expires_at: input.expires_at
+	    .unwrap_or( default_now + MAX_DELEGATION_LIFETIME ),
+    };
+    let entity = create_entity( &delegation )?;
+
+    { // Path via App's delegations
+	entity.link_from( &input.for_app, LinkTypes::AppToPublishingDelegation, None )?;
+    }
+
+    Ok( entity )
+}
+
+
+/// Get the active publishing delegations of an App
+#[hdk_extern]
+pub fn get_publishing_delegations(input: GetForAppInput) -> ExternResult<Vec<Entity<PublishingDelegationEntry>>> {
+    let collection = get_entities(
+        &input.for_app,
+        LinkTypes::AppToPublishingDelegation,
+        None
+    )?;
+
+    Ok( collection )
+}
+
+
+/// Revoke a publishing delegation
+///
+/// Versions already published under the delegation remain valid.  Validation cannot observe the
+/// deletion, so a modified client can keep using the delegation until it expires (at most
+/// [`MAX_DELEGATION_LIFETIME`] after it was issued).
+#[hdk_extern]
+pub fn revoke_publishing_delegation(input: GetEntityInput) -> ExternResult<ActionHash> {
+    debug!("Revoking publishing delegation: {}", input.id );
+    let entity : Entity<PublishingDelegationEntry> = get_entity( &input.id )?;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            entity.content.for_app.clone(),
+            LinkTypes::AppToPublishingDelegation,
+        )?.build()
+    )?;
+
+    for link in links {
+        if link.target == entity.id.clone().into() {
+            delete_link( link.create_link_hash, GetOptions::default() )?;
+        }
+    }
+
+    delete_entity::<PublishingDelegationEntry, EntryTypes>( &input.id )
+}
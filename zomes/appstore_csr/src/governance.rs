@@ -0,0 +1,125 @@
+use crate::{
+    hdk,
+    viewpoint::{
+        get_removed_subject_ids,
+    },
+};
+
+use std::collections::HashSet;
+use hdk::prelude::*;
+use appstore::{
+    LinkTypes,
+    GOVERNANCE_ANCHOR,
+    AppEntry,
+
+    hc_crud::{
+        get_entity,
+        Entity,
+    },
+    hdi_extensions::{
+        AnyLinkableHashTransformer,
+    },
+};
+
+
+
+/// Get the governance group registered by one of the DNA's governance agents
+///
+/// Only governance agents can create [`LinkTypes::GovernanceToGroup`] links so the earliest
+/// registration is used.
+pub(crate) fn governance_group() -> ExternResult<Option<ActionHash>> {
+    if appstore::governance_agents()?.is_empty() {
+        return Ok( None );
+    }
+
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            GOVERNANCE_ANCHOR.path_entry_hash()?,
+            LinkTypes::GovernanceToGroup,
+        )?.build()
+    )?;
+
+    Ok( match links.into_iter().min_by_key( |link| link.timestamp ) {
+        Some(link) => Some( link.target.must_be_action_hash()? ),
+        None => None,
+    })
+}
+
+
+/// Get the IDs of all apps removed by the governance group
+///
+/// Returns an empty set when no governance group has been registered.
+pub(crate) fn get_governance_removed_app_ids() -> ExternResult<HashSet<ActionHash>> {
+    match governance_group()? {
+        Some(group_id) => get_removed_subject_ids( &group_id, "app" ),
+        None => Ok( HashSet::new() ),
+    }
+}
+
+
+/// Check if the given App ID has been removed by the governance group
+pub(crate) fn is_governance_removed(app_id: &ActionHash) -> ExternResult<bool> {
+    Ok( get_governance_removed_app_ids()?.contains( app_id ) )
+}
+
+
+/// Remove any apps that have been taken down by the governance group
+pub(crate) fn filter_governance_removed(
+    apps: Vec<Entity<AppEntry>>
+) -> ExternResult<Vec<Entity<AppEntry>>> {
+    let removed_app_ids = get_governance_removed_app_ids()?;
+
+    if removed_app_ids.is_empty() {
+        return Ok( apps );
+    }
+
+    Ok(
+        apps.into_iter()
+            .filter(|entity| !removed_app_ids.contains( &entity.id ) )
+            .collect()
+    )
+}
+
+
+/// Get the governance agents configured in the DNA properties
+#[hdk_extern]
+pub fn get_governance_agents(_: ()) -> ExternResult<Vec<AgentPubKey>> {
+    appstore::governance_agents()
+}
+
+
+/// Get the governance group ID registered by the governance agents
+#[hdk_extern]
+pub fn get_governance_group(_: ()) -> ExternResult<Option<ActionHash>> {
+    governance_group()
+}
+
+
+/// Register a Group (founded by a governance agent with only governance agents as contributors)
+/// as the governance group
+#[hdk_extern]
+pub fn register_governance_group(group_id: ActionHash) -> ExternResult<ActionHash> {
+    debug!("Registering governance group: {}", group_id );
+    create_link(
+        GOVERNANCE_ANCHOR.path_entry_hash()?,
+        group_id,
+        LinkTypes::GovernanceToGroup,
+        (),
+    )
+}
+
+
+/// Get all apps that have been removed by the governance group
+#[hdk_extern]
+pub fn get_governance_removed_apps(_: ()) -> ExternResult<Vec<Entity<AppEntry>>> {
+    let mut app_ids : Vec<ActionHash> = get_governance_removed_app_ids()?
+        .into_iter()
+        .collect();
+    app_ids.sort();
+
+    let apps = app_ids.iter()
+        .filter_map(|app_id| get_entity( app_id ).ok() )
+        .collect();
+
+    Ok( apps )
+}
@@ -0,0 +1,172 @@
+use crate::{
+    hdk,
+};
+
+use std::cmp::Ordering;
+use hdk::prelude::*;
+use hdk_extensions::{
+    agent_id,
+};
+use appstore::{
+    EntryTypesUnit,
+    HostLivenessEntry,
+
+    hc_crud::{
+        now,
+        Entity,
+        EntryModel,
+    },
+    hdi_extensions::{
+        ScopedTypeConnector,
+    },
+};
+pub use appstore_sdk::{
+    RecordHostResponseInput,
+    GetRankedHostsInput,
+    RankedHost,
+};
+
+
+/// Get the latest host liveness records from this agent's source chain
+pub(crate) fn get_host_liveness_handler() -> ExternResult<Vec<Entity<HostLivenessEntry>>> {
+    let records = query(
+        ChainQueryFilter::new()
+            .entry_type( EntryTypesUnit::HostLiveness.try_into()? )
+            .include_entries( true )
+    )?;
+    let mut entities : Vec<Entity<HostLivenessEntry>> = vec![];
+
+    for record in records {
+        let liveness = HostLivenessEntry::try_from( record.clone() )?;
+        let existing = entities.iter_mut()
+            .find( |entity| entity.content.is_for(
+                &liveness.dna, &liveness.zome, &liveness.function, &liveness.host
+            ));
+
+        match existing {
+            Some(entity) => {
+                entity.address = hash_entry( liveness.clone() )?;
+                entity.action = record.action_address().to_owned();
+                entity.content = liveness;
+            },
+            None => entities.push( Entity {
+                id: record.action_address().to_owned(),
+                address: hash_entry( liveness.clone() )?,
+                action: record.action_address().to_owned(),
+                ctype: liveness.get_type(),
+                content: liveness,
+            }),
+        }
+    }
+
+    Ok( entities )
+}
+
+
+/// Record whether a host answered a ping (or any call) for a DNA zome function
+#[hdk_extern]
+pub fn record_host_response(input: RecordHostResponseInput) -> ExternResult<Entity<HostLivenessEntry>> {
+    debug!("Recording host response ({}): {}", input.responded, input.host );
+    let current = get_host_liveness_handler()?.into_iter()
+        .find( |entity| entity.content.is_for(
+            &input.dna, &input.zome, &input.function, &input.host
+        ));
+    let default_now = now()?;
+
+    let mut liveness = match current.as_ref() {
+        Some(entity) => entity.content.clone(),
+        None => HostLivenessEntry {
+            dna: input.dna,
+            zome: input.zome,
+            function: input.function,
+            host: input.host,
+            last_pong: None,
+            last_failure: None,
+            failures: 0,
+            author: agent_id()?,
+            published_at: default_now,
+            last_updated: default_now,
+        },
+    };
+
+    if input.responded {
+        liveness.last_pong = Some( default_now );
+        liveness.failures = 0;
+    }
+    else {
+        liveness.last_failure = Some( default_now );
+        liveness.failures += 1;
+    }
+    liveness.last_updated = default_now;
+
+    let (id, action) = match current {
+        Some(entity) => (
+            entity.id,
+            update_entry( entity.action, liveness.clone().to_input() )?,
+        ),
+        None => {
+            let action = create_entry( liveness.clone().to_input() )?;
+            ( action.clone(), action )
+        },
+    };
+
+    Ok( Entity {
+        id: id,
+        address: hash_entry( liveness.clone() )?,
+        action: action,
+        ctype: liveness.get_type(),
+        content: liveness,
+    })
+}
+
+
+/// Responsive hosts first (most recent pong first), then unknown hosts, then failing hosts (fewest
+/// failures first)
+fn rank_hosts(a: &RankedHost, b: &RankedHost) -> Ordering {
+    let group = |host: &RankedHost| match (host.failures, host.last_pong) {
+        (0, Some(_)) => 0,
+        (0, None) => 1,
+        _ => 2,
+    };
+
+    group( a ).cmp( &group( b ) )
+        .then( b.last_pong.cmp( &a.last_pong ) )
+        .then( a.failures.cmp( &b.failures ) )
+        .then( a.last_failure.cmp( &b.last_failure ) )
+}
+
+/// Get hosts for a DNA zome function ranked by their recent availability
+#[hdk_extern]
+pub fn get_ranked_hosts(input: GetRankedHostsInput) -> ExternResult<Vec<RankedHost>> {
+    let records : Vec<HostLivenessEntry> = get_host_liveness_handler()?.into_iter()
+        .map( |entity| entity.content )
+        .filter( |liveness| liveness.dna == input.dna
+                  && liveness.zome == input.zome
+                  && liveness.function == input.function )
+        .collect();
+
+    let hosts = input.hosts.unwrap_or_else(
+        || records.iter().map( |liveness| liveness.host.clone() ).collect()
+    );
+
+    let mut ranked : Vec<RankedHost> = hosts.into_iter()
+        .map( |host| match records.iter().find( |liveness| liveness.host == host ) {
+            Some(liveness) => RankedHost {
+                host,
+                last_pong: liveness.last_pong,
+                last_failure: liveness.last_failure,
+                failures: liveness.failures,
+            },
+            None => RankedHost {
+                host,
+                last_pong: None,
+                last_failure: None,
+                failures: 0,
+            },
+        })
+        .collect();
+
+    ranked.sort_by( rank_hosts );
+
+    Ok( ranked )
+}
@@ -1,6 +1,20 @@
 pub mod publisher;
 pub mod app;
 pub mod app_version;
+pub mod moderation_log;
+pub mod appeal;
+pub mod governance;
+pub mod viewpoint_preference;
+pub mod viewpoint;
+pub mod apphub;
+pub mod host_liveness;
+pub mod mirror;
+pub mod delegation;
+pub mod schema;
+pub mod migration;
+pub mod snapshot;
+pub mod change_log;
+pub mod signal;
 
 pub use hdk_extensions::hdk;
 pub use appstore::{
@@ -11,11 +25,15 @@ pub use appstore::{
     hc_crud,
     hdi_extensions,
 };
-use std::collections::BTreeMap;
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
 use hdk::prelude::*;
 use hdi_extensions::{
     guest_error,
     trace_origin_root,
+    AnyLinkableHashTransformer,
 };
 use hdk_extensions::{
     UpdateEntryInput,
@@ -55,36 +73,78 @@ use apphub_sdk::{
         ZomeEntry,
     },
 };
+pub use appstore_sdk::{
+    GetForAgentInput,
+    GetForPublisherInput,
+    GetForAppInput,
+    GetModeratorActionsInput,
+    UpdateModeratorActionInput,
+};
 use coop_content_sdk::{
     GroupEntry,
     call_local_zome_decode,
     create_group, update_group,
     register_content_to_group,
     register_content_update_to_group,
-    get_all_group_content_latest,
 };
 
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GetForAgentInput {
-    pub for_agent: AgentPubKey,
-}
+/// Create an unrestricted capability grant for the read-only externs
+fn grant_read_only_access() -> ExternResult<ActionHash> {
+    let zome_name = zome_info()?.name;
+    let functions = match appstore::read_only_functions()? {
+        Some(names) => names,
+        None => READ_ONLY_FUNCTIONS.iter()
+            .map( |name| name.to_string() )
+            .collect(),
+    };
+    let mut granted = BTreeSet::new();
 
+    for name in functions {
+        granted.insert( (zome_name.clone(), FunctionName::new( name )) );
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GetForPublisherInput {
-    pub for_publisher: EntityId,
+    create_cap_grant( CapGrantEntry {
+        tag: "read_only".to_string(),
+        access: CapAccess::Unrestricted,
+        functions: GrantedFunctions::Listed( granted ),
+    })
 }
 
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GetForAppInput {
-    pub for_app: EntityId,
+/// Get the externs in this agent's read-only capability grant
+#[hdk_extern]
+pub fn get_read_only_functions(_: ()) -> ExternResult<Vec<String>> {
+    let records = query(
+        ChainQueryFilter::new()
+            .action_type( ActionType::Create )
+            .entry_type( EntryType::CapGrant )
+            .include_entries( true )
+    )?;
+    let mut functions = vec![];
+
+    for record in records {
+        if let Some(Entry::CapGrant( grant )) = record.entry().as_option() {
+            if grant.tag != "read_only" {
+                continue;
+            }
+
+            if let GrantedFunctions::Listed( listed ) = &grant.functions {
+                functions.extend( listed.iter().map( |(_, name)| name.0.clone() ) );
+            }
+        }
+    }
+
+    functions.sort();
+
+    Ok( functions )
 }
 
 
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
+    grant_read_only_access()?;
+
     Ok(InitCallbackResult::Pass)
 }
 
@@ -152,7 +212,7 @@ pub fn get_apps_for_agent(input: GetForAgentInput) -> ExternResult<Vec<Entity<Ap
         None
     )?;
 
-    Ok( collection )
+    Ok( governance::filter_governance_removed( collection )? )
 }
 
 /// Get Apps that belong to the given Publisher ID
@@ -164,7 +224,7 @@ pub fn get_apps_for_publisher(input: GetForPublisherInput) -> ExternResult<Vec<E
         None
     )?;
 
-    Ok( collection )
+    Ok( governance::filter_governance_removed( collection )? )
 }
 
 /// Get Apps that the current cell agent maintains
@@ -178,6 +238,12 @@ pub fn get_my_apps(_:()) -> ExternResult<Vec<Entity<AppEntry>>> {
 /// Get all Apps
 #[hdk_extern]
 pub fn get_all_apps(filter_deprecated: Option<bool>) -> ExternResult<Vec<Entity<AppEntry>>> {
+    let collection = get_all_apps_handler( filter_deprecated )?;
+
+    Ok( governance::filter_governance_removed( collection )? )
+}
+
+pub(crate) fn get_all_apps_handler(filter_deprecated: Option<bool>) -> ExternResult<Vec<Entity<AppEntry>>> {
     let mut collection = hc_crud::get_entities(
         &ALL_APPS_ANCHOR.path_entry_hash()?,
         LinkTypes::AllAppsToApp,
@@ -213,22 +279,22 @@ pub fn get_app_versions_for_app(input: GetForAppInput) -> ExternResult<Vec<Entit
 }
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GetModeratorActionsInput {
-    pub group_id: ActionHash,
-    pub app_id: ActionHash,
+fn get_moderator_actions_handler(input: GetModeratorActionsInput) -> ExternResult<Vec<Entity<ModeratorActionEntry>>> {
+    get_subject_moderator_actions( &input.group_id, format!("app::{}", input.app_id ) )
 }
 
-fn get_moderator_actions_handler(input: GetModeratorActionsInput) -> ExternResult<Vec<Entity<ModeratorActionEntry>>> {
+pub(crate) fn get_subject_moderator_actions(
+    group_id: &ActionHash,
+    tag: String,
+) -> ExternResult<Vec<Entity<ModeratorActionEntry>>> {
     // - Find group anchor
-    // - Find moderator action link with tag 'app::<app_id>'
+    // - Find moderator action link with tag '<subject type>::<subject_id>'
     // - Follow evolutions for group
     let group_anchor_entry = GroupAnchorEntry {
-        group_id: input.group_id.clone(),
+        group_id: group_id.clone(),
     };
     let group_anchor_hash = hash_entry( &group_anchor_entry )?;
 
-    let tag = format!("app::{}", input.app_id );
     let moderator_action_links = get_links(
         GetLinksInputBuilder::try_new(
             group_anchor_hash.clone(),
@@ -243,40 +309,52 @@ fn get_moderator_actions_handler(input: GetModeratorActionsInput) -> ExternResul
         .min_by_key( |link| link.timestamp );
 
     Ok( match mayby_action_history {
-        Some(link) => {
-            type Response = Vec<ActionHash>;
-            let history = call_local_zome_decode!(
-                Response,
-                "coop_content_csr",
-                "get_group_content_evolutions",
-                coop_content_sdk::GetGroupContentInput {
-                    group_id: input.group_id.clone(),
-                    content_id: link.target.clone(),
-                    full_trace: None,
-                }
-            )?;
-
-            history
-                .into_iter()
-                .filter_map(|addr| {
-                    let entry = ModeratorActionEntry::try_from( must_get( &addr ).ok()? ).ok()?;
-                    Some(
-                        Entity {
-                            id: addr.clone(),
-                            address: hash_entry( entry.clone() ).ok()?,
-                            action: addr,
-                            ctype: entry.get_type(),
-                            content: entry,
-                        }
-                    )
-                })
-                .collect()
-        },
+        Some(link) => get_moderator_action_history(
+            group_id,
+            link.target.clone().must_be_action_hash()?,
+        )?,
         None => vec![],
     })
 }
 
 
+/// Get the evolutions of a Moderator Action (identified by its origin) within the given Group
+pub(crate) fn get_moderator_action_history(
+    group_id: &ActionHash,
+    origin: ActionHash,
+) -> ExternResult<Vec<Entity<ModeratorActionEntry>>> {
+    type Response = Vec<ActionHash>;
+    let history = call_local_zome_decode!(
+        Response,
+        "coop_content_csr",
+        "get_group_content_evolutions",
+        coop_content_sdk::GetGroupContentInput {
+            group_id: group_id.clone(),
+            content_id: origin.into(),
+            full_trace: None,
+        }
+    )?;
+
+    Ok(
+        history
+            .into_iter()
+            .filter_map(|addr| {
+                let entry = ModeratorActionEntry::try_from( must_get( &addr ).ok()? ).ok()?;
+                Some(
+                    Entity {
+                        id: addr.clone(),
+                        address: hash_entry( entry.clone() ).ok()?,
+                        action: addr,
+                        ctype: entry.get_type(),
+                        content: entry,
+                    }
+                )
+            })
+            .collect()
+    )
+}
+
+
 /// Get moderator actions for the given App ID that were created by the members of the given Group
 /// ID
 #[hdk_extern]
@@ -297,30 +375,36 @@ pub fn get_moderated_state(input: GetModeratorActionsInput) -> ExternResult<Opti
 }
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UpdateModeratorActionInput {
-    pub group_id: ActionHash,
-    pub app_id: ActionHash,
-    pub message: String,
-    pub metadata: BTreeMap<String, RmpvValue>,
-}
-
 /// Update the moderated state for the given App ID from the viewpoint of the given Groupd ID
 #[hdk_extern]
 pub fn update_moderated_state(input: UpdateModeratorActionInput) -> ExternResult<Entity<ModeratorActionEntry>> {
-    let actions = get_moderator_actions_handler( GetModeratorActionsInput {
-        group_id: input.group_id.clone(),
-        app_id: input.app_id.clone(),
-    })?;
-
-    let group_rev = follow_evolutions( &input.group_id )?.last().unwrap().to_owned();
+    update_subject_moderated_state(
+        input.group_id,
+        "app",
+        input.app_id,
+        input.message,
+        input.metadata,
+    )
+}
+
+pub(crate) fn update_subject_moderated_state(
+    group_id: ActionHash,
+    subject_type: &str,
+    subject_id: ActionHash,
+    message: String,
+    metadata: BTreeMap<String, RmpvValue>,
+) -> ExternResult<Entity<ModeratorActionEntry>> {
+    let tag = format!("{}::{}", subject_type, subject_id );
+    let actions = get_subject_moderator_actions( &group_id, tag.clone() )?;
+
+    let group_rev = follow_evolutions( &group_id )?.last().unwrap().to_owned();
     let ma_entry = ModeratorActionEntry {
-        group_id: (input.group_id.clone(), group_rev),
+        group_id: (group_id.clone(), group_rev),
         author: agent_id()?,
         published_at: hc_crud::now()?,
-        message: input.message,
-        subject_id: input.app_id.clone(),
-        metadata: input.metadata,
+        message: message,
+        subject_id: subject_id,
+        metadata: metadata,
     };
 
     if actions.len() > 0 {
@@ -340,13 +424,15 @@ pub fn update_moderated_state(input: UpdateModeratorActionInput) -> ExternResult
             content: ma_entry,
         };
 
+        viewpoint::update_removed_subject_index( subject_type, &entity )?;
+
         Ok( entity )
     }
     else {
         let entity = hc_crud::create_entity( &ma_entry )?;
 
         let group_anchor_entry = GroupAnchorEntry {
-            group_id: input.group_id,
+            group_id: group_id,
         };
         let group_anchor_hash = hash_entry( &group_anchor_entry )?;
 
@@ -368,7 +454,6 @@ pub fn update_moderated_state(input: UpdateModeratorActionInput) -> ExternResult
             content_base: None,
         })?;
 
-        let tag = format!("app::{}", input.app_id );
         create_link(
             group_anchor_hash,
             entity.id.clone(),
@@ -376,12 +461,13 @@ pub fn update_moderated_state(input: UpdateModeratorActionInput) -> ExternResult
             tag.into_bytes()
         )?;
 
+        viewpoint::update_removed_subject_index( subject_type, &entity )?;
+
         Ok( entity )
     }
 }
 
 
-
 //
 // Group CRUD
 //
@@ -453,76 +539,14 @@ pub fn update_group(input: UpdateEntryInput<GroupEntry>) -> ExternResult<Entity<
 /// Get all apps from the perspective of the given Group ID
 #[hdk_extern]
 pub fn viewpoint_get_all_apps(group_id: ActionHash) -> ExternResult<Vec<Entity<AppEntry>>> {
-    // - Derive group anchor
-    // - Get moderator action links
-    // - Get all group content
-    let removed_app_ids : Vec<ActionHash> = get_all_group_content_latest!({
-        group_id: group_id.clone(),
-        content_type: None,
-        content_base: None,
-    })?.into_iter()
-        .filter_map(|(_origin, latest)| {
-            debug!("Get latest group entry: {}", group_id );
-            let addr = latest.into_action_hash()?;
-            let record = must_get( &addr ).ok()?;
-            Some( ModeratorActionEntry::try_from( record ).ok()? )
-        })
-        .filter_map(|moderator_action| {
-            match moderator_action.metadata.get("remove")? {
-                RmpvValue::Boolean(value) => match value {
-                    true => Some( moderator_action.subject_id ),
-                    false => None,
-                },
-                _ => None,
-            }
-        })
-        .collect();
-
-    debug!("Removed app IDs from viewpoint {}: {:#?}", group_id, removed_app_ids );
-    let apps = get_all_apps(None)?
-        .into_iter()
-        .filter(|entity| !removed_app_ids.contains( &entity.id ) )
-        .collect();
-
-    Ok( apps )
+    viewpoint::get_viewpoint_apps( &[ group_id ] )
 }
 
 
 /// Get all removed apps from the perspective of the given Group ID
 #[hdk_extern]
 pub fn viewpoint_get_all_removed_apps(group_id: ActionHash) -> ExternResult<Vec<Entity<AppEntry>>> {
-    // - Derive group anchor
-    // - Get moderator action links
-    // - Get all group content
-    let removed_app_ids : Vec<ActionHash> = get_all_group_content_latest!({
-        group_id: group_id.clone(),
-        content_type: None,
-        content_base: None,
-    })?.into_iter()
-        .filter_map(|(_origin, latest)| {
-            debug!("Get latest group entry: {}", group_id );
-            let addr = latest.into_action_hash()?;
-            let record = must_get( &addr ).ok()?;
-            Some( ModeratorActionEntry::try_from( record ).ok()? )
-        })
-        .filter_map(|moderator_action| {
-            match moderator_action.metadata.get("remove")? {
-                RmpvValue::Boolean(value) => match value {
-                    true => Some( moderator_action.subject_id ),
-                    false => None,
-                },
-                _ => None,
-            }
-        })
-        .collect();
-
-    debug!("Removed app IDs from viewpoint {}: {:#?}", group_id, removed_app_ids );
-    let apps = get_all_apps(None)?
-        .into_iter()
-        .filter(|entity| removed_app_ids.contains( &entity.id ) )
-        .collect();
-
-    Ok( apps )
+    viewpoint::get_viewpoint_removed_apps( &[ group_id ] )
 }
 
 
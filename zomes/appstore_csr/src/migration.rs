@@ -0,0 +1,364 @@
+use crate::{
+    hdk,
+    get_my_publishers,
+    get_my_apps,
+    get_app_versions_for_app,
+    GetForAppInput,
+    publisher::{
+        create_publisher,
+        deprecate_publisher,
+    },
+    app::{
+        create_app,
+        deprecate_app,
+    },
+    app_version::{
+        create_app_version,
+    },
+};
+
+use std::collections::BTreeMap;
+use hdk::prelude::*;
+use hdk_extensions::{
+    agent_id,
+};
+use appstore::{
+    AppStoreError,
+    Provenance,
+    PublisherEntry,
+    AppEntry,
+    AppVersionEntry,
+
+    hc_crud::{
+        Entity,
+    },
+};
+pub use appstore_sdk::{
+    CreatePublisherInput,
+    CreateAppInput,
+    CreateAppVersionInput,
+    DeprecatePublisherInput,
+    DeprecateAppInput,
+    MigrationMemory,
+    MigrationExport,
+    MigrationImportReport,
+    OpenChainInput,
+};
+use coop_content_sdk::{
+    call_local_zome_decode,
+};
+
+
+fn provenance_of<T>(dna: &DnaHash, entity: &Entity<T>, author: &AgentPubKey) -> Provenance {
+    Provenance {
+        dna: dna.to_owned(),
+        id: entity.id.to_owned(),
+        action: entity.action.to_owned(),
+        author: author.to_owned(),
+    }
+}
+
+
+/// The original ID of an entity that was imported from `dna`, paired with the copy's ID
+fn imported_from(
+    dna: &DnaHash,
+    provenance: &Option<Provenance>,
+    id: &ActionHash,
+) -> Option<(ActionHash, ActionHash)> {
+    provenance.as_ref()
+        .filter( |provenance| provenance.dna == *dna )
+        .map( |provenance| (provenance.id.clone(), id.clone()) )
+}
+
+
+/// Point references at the imported copies (references outside the import are kept as-is)
+fn rewrite_ids(
+    ids: Option<Vec<ActionHash>>,
+    imported: &BTreeMap<ActionHash, ActionHash>,
+) -> Option<Vec<ActionHash>> {
+    ids.map( |ids| ids.into_iter()
+        .map( |id| imported.get( &id ).cloned().unwrap_or( id ) )
+        .collect()
+    )
+}
+
+
+/// Get the bytes of every icon referenced by the given Publishers and Apps
+pub(crate) fn export_icons(
+    publishers: &Vec<Entity<PublisherEntry>>,
+    apps: &Vec<Entity<AppEntry>>,
+) -> ExternResult<Vec<MigrationMemory>> {
+    let mut icons : Vec<EntryHash> = publishers.iter()
+        .filter_map( |entity| entity.content.icon.clone() )
+        .chain( apps.iter().map( |entity| entity.content.icon.clone() ) )
+        .collect();
+    icons.sort();
+    icons.dedup();
+
+    let mut memories = vec![];
+
+    for address in icons {
+        let bytes = call_local_zome_decode!(
+            SerializedBytes,
+            "mere_memory_api",
+            "remember",
+            address.clone()
+        )?;
+
+        memories.push( MigrationMemory {
+            address,
+            bytes,
+        });
+    }
+
+    Ok( memories )
+}
+
+
+/// Export this agent's Publishers, Apps and App Versions (with their icons) for a DNA migration
+#[hdk_extern]
+pub fn export_my_entries(_: ()) -> ExternResult<MigrationExport> {
+    let publishers = get_my_publishers(())?;
+    let apps = get_my_apps(())?;
+    let mut app_versions = vec![];
+
+    for app in apps.iter() {
+        app_versions.extend( get_app_versions_for_app( GetForAppInput {
+            for_app: app.id.clone(),
+        })? );
+    }
+
+    let memories = export_icons( &publishers, &apps )?;
+
+    Ok( MigrationExport {
+        dna: dna_info()?.hash,
+        author: agent_id()?,
+        publishers,
+        apps,
+        app_versions,
+        memories,
+    })
+}
+
+
+/// Close this agent's chain, pointing at the DNA that replaces this one
+#[hdk_extern]
+pub fn close_my_chain(new_dna: DnaHash) -> ExternResult<ActionHash> {
+    debug!("Closing chain for migration to DNA: {}", new_dna );
+    close_chain( Some( MigrationTarget::Dna( new_dna ) ) )
+}
+
+
+/// Record that this agent's chain continues from a closed chain in the previous DNA
+#[hdk_extern]
+pub fn open_my_chain(input: OpenChainInput) -> ExternResult<ActionHash> {
+    debug!("Opening chain migrated from DNA: {}", input.previous_dna );
+    open_chain( MigrationTarget::Dna( input.previous_dna ), input.close_action )
+}
+
+
+/// Re-create entities from another DNA with a [`Provenance`] pointing at the originals
+///
+/// Original `published_at`/`last_updated` values, deprecation notices and manifest summaries are
+/// kept.  References between the entities (icons, publishers, apps and recommended alternatives)
+/// are rewritten to their new addresses, including those imported by earlier calls.  Entities
+/// that already have a copy from `dna` are skipped so that repeating an import is harmless.  The
+/// importing agent is added to the editors so that it is allowed to author the copies.
+pub(crate) fn import_entities(
+    dna: &DnaHash,
+    publishers: Vec<Entity<PublisherEntry>>,
+    apps: Vec<Entity<AppEntry>>,
+    app_versions: Vec<Entity<AppVersionEntry>>,
+    memories: Vec<MigrationMemory>,
+) -> ExternResult<MigrationImportReport> {
+    let agent = agent_id()?;
+    let with_agent = |mut editors: Vec<AgentPubKey>| {
+        if !editors.contains( &agent ) {
+            editors.push( agent.clone() );
+        }
+        editors
+    };
+    let mut icons : BTreeMap<EntryHash, EntryHash> = BTreeMap::new();
+    // Entities imported by an earlier call (eg. a previous snapshot page) are found through the
+    // provenance of this agent's copies
+    let mut publisher_ids : BTreeMap<ActionHash, ActionHash> = get_my_publishers(())?.into_iter()
+        .filter_map( |entity| imported_from( dna, &entity.content.provenance, &entity.id ) )
+        .collect();
+    let mut app_ids : BTreeMap<ActionHash, ActionHash> = get_my_apps(())?.into_iter()
+        .filter_map( |entity| imported_from( dna, &entity.content.provenance, &entity.id ) )
+        .collect();
+    let mut deprecated_publishers = vec![];
+    let mut deprecated_apps = vec![];
+    let mut report = MigrationImportReport {
+        publishers: vec![],
+        apps: vec![],
+        app_versions: vec![],
+    };
+
+    for memory in memories {
+        let address : EntryHash = call_local_zome_decode!(
+            EntryHash,
+            "mere_memory_api",
+            "save",
+            memory.bytes
+        )?;
+
+        icons.insert( memory.address, address );
+    }
+
+    for entity in publishers {
+        if publisher_ids.contains_key( &entity.id ) {
+            continue;
+        }
+
+        let provenance = provenance_of( dna, &entity, &entity.content.author );
+        let content : PublisherEntry = entity.content;
+        let imported = create_publisher( CreatePublisherInput {
+            name: content.name,
+            location: content.location,
+            website: content.website,
+            description: content.description,
+            email: content.email,
+            icon: content.icon
+                .map( |icon| icons.get( &icon ).cloned().unwrap_or( icon ) ),
+            editors: Some( with_agent( content.editors ) ),
+            published_at: Some( content.published_at ),
+            last_updated: Some( content.last_updated ),
+            metadata: Some( content.metadata ),
+            provenance: Some( provenance ),
+        })?;
+
+        if let Some(notice) = content.deprecation {
+            deprecated_publishers.push( (imported.id.clone(), imported.action.clone(), notice) );
+        }
+
+        publisher_ids.insert( entity.id, imported.id.clone() );
+        report.publishers.push( imported );
+    }
+
+    // Deprecate after every copy exists so that recommended alternatives can be rewritten
+    for (id, action, notice) in deprecated_publishers {
+        let deprecated = deprecate_publisher( DeprecatePublisherInput {
+            base: action,
+            message: notice.message,
+            recommended_alternatives: rewrite_ids( notice.recommended_alternatives, &publisher_ids ),
+        })?;
+
+        if let Some(publisher) = report.publishers.iter_mut().find( |publisher| publisher.id == id ) {
+            *publisher = deprecated;
+        }
+    }
+
+    for entity in apps {
+        if app_ids.contains_key( &entity.id ) {
+            continue;
+        }
+
+        let provenance = provenance_of( dna, &entity, &entity.content.author );
+        let content : AppEntry = entity.content;
+        let publisher = publisher_ids.get( &content.publisher ).cloned()
+            .ok_or( AppStoreError::MissingImportParent {
+                entity_type: "App".to_string(),
+                entity: entity.id.clone(),
+                parent_type: "Publisher".to_string(),
+                parent: content.publisher.clone(),
+            })?;
+        let imported = create_app( CreateAppInput {
+            title: content.title,
+            subtitle: content.subtitle,
+            description: content.description,
+            icon: icons.get( &content.icon ).cloned().unwrap_or( content.icon ),
+            publisher,
+            apphub_hrl: content.apphub_hrl,
+            apphub_hrl_hash: content.apphub_hrl_hash,
+            editors: Some( with_agent( content.editors ) ),
+            published_at: Some( content.published_at ),
+            last_updated: Some( content.last_updated ),
+            metadata: Some( content.metadata ),
+            // The ownership proof signs the old publisher ID so it cannot be carried over
+            source_ownership: None,
+            prove_source_ownership: None,
+            provenance: Some( provenance ),
+        })?;
+
+        if let Some(notice) = content.deprecation {
+            deprecated_apps.push( (imported.id.clone(), imported.action.clone(), notice) );
+        }
+
+        app_ids.insert( entity.id, imported.id.clone() );
+        report.apps.push( imported );
+    }
+
+    for (id, action, notice) in deprecated_apps {
+        let deprecated = deprecate_app( DeprecateAppInput {
+            base: action,
+            message: notice.message,
+            recommended_alternatives: rewrite_ids( notice.recommended_alternatives, &app_ids ),
+        })?;
+
+        if let Some(app) = report.apps.iter_mut().find( |app| app.id == id ) {
+            *app = deprecated;
+        }
+    }
+
+    let mut app_version_ids : BTreeMap<ActionHash, ActionHash> = BTreeMap::new();
+
+    for app_id in app_ids.values() {
+        app_version_ids.extend(
+            get_app_versions_for_app( GetForAppInput {
+                for_app: app_id.clone(),
+            })?.into_iter()
+                .filter_map( |entity| imported_from( dna, &entity.content.provenance, &entity.id ) )
+        );
+    }
+
+    for entity in app_versions {
+        if app_version_ids.contains_key( &entity.id ) {
+            continue;
+        }
+
+        let provenance = provenance_of( dna, &entity, &entity.content.author );
+        let content : AppVersionEntry = entity.content;
+        let for_app = app_ids.get( &content.for_app ).cloned()
+            .ok_or( AppStoreError::MissingImportParent {
+                entity_type: "App Version".to_string(),
+                entity: entity.id.clone(),
+                parent_type: "App".to_string(),
+                parent: content.for_app.clone(),
+            })?;
+        let imported = create_app_version( CreateAppVersionInput {
+            version: content.version,
+            for_app,
+            apphub_hrl: content.apphub_hrl,
+            apphub_hrl_hash: content.apphub_hrl_hash,
+            bundle_hashes: content.bundle_hashes,
+            published_at: Some( content.published_at ),
+            last_updated: Some( content.last_updated ),
+            metadata: Some( content.metadata ),
+            verify: None,
+            dna_hashes: content.dna_hashes,
+            delegation: None,
+            provenance: Some( provenance ),
+            manifest: content.manifest,
+        })?;
+
+        app_version_ids.insert( entity.id, imported.id.clone() );
+        report.app_versions.push( imported );
+    }
+
+    Ok( report )
+}
+
+
+/// Re-create this agent's exported entities in this DNA (see [`import_entities`])
+#[hdk_extern]
+pub fn import_my_entries(export: MigrationExport) -> ExternResult<MigrationImportReport> {
+    debug!("Importing entities from DNA: {}", export.dna );
+    import_entities(
+        &export.dna,
+        export.publishers,
+        export.apps,
+        export.app_versions,
+        export.memories,
+    )
+}
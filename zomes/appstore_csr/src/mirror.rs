@@ -0,0 +1,208 @@
+use crate::{
+    hdk,
+    hdi_extensions,
+    apphub::{
+        portal_remote_call,
+        calculate_hash,
+        get_app_version_webapp,
+    },
+};
+
+use hdk::prelude::*;
+use hdi_extensions::{
+    AnyLinkableHashTransformer,
+};
+use appstore::{
+    LinkTypes,
+    AppStoreError,
+    AppVersionEntry,
+
+    hc_crud::{
+        get_entity,
+        Entity,
+        GetEntityInput,
+    },
+};
+pub use appstore_sdk::{
+    MirrorAppVersionInput,
+};
+use coop_content_sdk::{
+    call_local_zome_decode,
+};
+
+
+
+/// Get the addresses of the local mere_memory mirrors of an App Version's webhapp bundle
+pub(crate) fn get_mirror_addresses(app_version_id: &ActionHash) -> ExternResult<Vec<EntryHash>> {
+    let mirror_links = get_links(
+        GetLinksInputBuilder::try_new(
+            app_version_id.clone(),
+            LinkTypes::AppVersionToMirror,
+        )?.build()
+    )?;
+
+    mirror_links.into_iter()
+        .map( |link| link.target.must_be_entry_hash() )
+        .collect()
+}
+
+
+/// Fetch the webhapp bundle of an App Version from a DevHub host and check its hash
+fn get_hosted_bundle(app_version: &AppVersionEntry) -> ExternResult<SerializedBytes> {
+    let (webapp_version, _) = get_app_version_webapp( app_version )?;
+    let bundle : SerializedBytes = portal_remote_call(
+        &app_version.apphub_hrl.dna,
+        "apphub_csr",
+        "get_webhapp_bundle",
+        webapp_version.webapp,
+    )?;
+    let calc_hash = calculate_hash( bundle.bytes().to_owned() )?;
+
+    if calc_hash != app_version.bundle_hashes.hash {
+        return Err( AppStoreError::BundleHashMismatch {
+            expected: app_version.bundle_hashes.hash.clone(),
+            received: calc_hash.clone(),
+        }.into() );
+    }
+
+    Ok( bundle )
+}
+
+
+/// Read a mirrored webhapp bundle and check it against the App Version's bundle hash
+///
+/// The mirror link only proves that the memory entry claims the right hash, so the bytes are
+/// rehashed.  Returns `None` for a missing or mismatched mirror.
+fn get_verified_mirror(
+    app_version: &AppVersionEntry,
+    address: &EntryHash,
+) -> ExternResult<Option<SerializedBytes>> {
+    let bundle : SerializedBytes = match call_local_zome_decode!(
+        SerializedBytes,
+        "mere_memory_api",
+        "remember",
+        address.clone()
+    ) {
+        Ok(bundle) => bundle,
+        Err(err) => {
+            debug!("Skipping unreadable mirror ({}): {:?}", address, err );
+            return Ok( None );
+        },
+    };
+    let calc_hash = calculate_hash( bundle.bytes().to_owned() )?;
+
+    if calc_hash != app_version.bundle_hashes.hash {
+        debug!(
+            "Skipping mirror ({}) with mismatched hash: {} != {}",
+            address, calc_hash, app_version.bundle_hashes.hash,
+        );
+        return Ok( None );
+    }
+
+    Ok( Some( bundle ) )
+}
+
+
+/// Get the first verified mirror (address and bundle) of an App Version
+fn find_verified_mirror(
+    entity: &Entity<AppVersionEntry>,
+) -> ExternResult<Option<(EntryHash, SerializedBytes)>> {
+    for address in get_mirror_addresses( &entity.id )? {
+        if let Some(bundle) = get_verified_mirror( &entity.content, &address )? {
+            return Ok( Some( (address, bundle) ) );
+        }
+    }
+
+    Ok( None )
+}
+
+
+/// Read the webhapp bundle of an App Version from a verified local mere_memory mirror
+fn get_mirrored_bundle(entity: &Entity<AppVersionEntry>) -> ExternResult<SerializedBytes> {
+    let (_, bundle) = find_verified_mirror( entity )?
+        .ok_or( AppStoreError::MissingVerifiedMirror {
+            app_version: entity.id.clone(),
+        })?;
+
+    Ok( bundle )
+}
+
+
+/// Pin an App Version's webhapp bundle into this DNA's mere_memory (opt-in mirroring)
+///
+/// When an `address` is given, that (already saved) memory is linked instead of fetching the
+/// bundle from a DevHub host.  Either way the bytes must match the App Version's bundle hash.
+#[hdk_extern]
+pub fn mirror_app_version(input: MirrorAppVersionInput) -> ExternResult<EntryHash> {
+    debug!("Mirroring AppVersion: {}", input.id );
+    let entity : Entity<AppVersionEntry> = get_entity( &input.id )?;
+
+    if let Some((address, _)) = find_verified_mirror( &entity )? {
+        return Ok( address );
+    }
+
+    let address = match input.address {
+        Some(address) => {
+            if get_verified_mirror( &entity.content, &address )?.is_none() {
+                return Err( AppStoreError::UnverifiedMirror {
+                    mirror: address,
+                    expected: entity.content.bundle_hashes.hash.clone(),
+                }.into() );
+            }
+
+            address
+        },
+        None => {
+            let bundle = get_hosted_bundle( &entity.content )?;
+
+            call_local_zome_decode!(
+                EntryHash,
+                "mere_memory_api",
+                "save",
+                bundle
+            )?
+        },
+    };
+
+    create_link(
+        entity.id,
+        address.clone(),
+        LinkTypes::AppVersionToMirror,
+        (),
+    )?;
+
+    Ok( address )
+}
+
+
+/// Get the verified mirror addresses of an App Version
+#[hdk_extern]
+pub fn get_app_version_mirrors(input: GetEntityInput) -> ExternResult<Vec<EntryHash>> {
+    let entity : Entity<AppVersionEntry> = get_entity( &input.id )?;
+    let mut addresses = vec![];
+
+    for address in get_mirror_addresses( &entity.id )? {
+        if get_verified_mirror( &entity.content, &address )?.is_some() {
+            addresses.push( address );
+        }
+    }
+
+    Ok( addresses )
+}
+
+
+/// Get the webhapp bundle of an App Version from a DevHub host, falling back to a verified mirror
+/// when the hosts are unreachable
+#[hdk_extern]
+pub fn get_app_version_bundle(input: GetEntityInput) -> ExternResult<SerializedBytes> {
+    debug!("Get bundle for AppVersion: {}", input.id );
+    let entity : Entity<AppVersionEntry> = get_entity( &input.id )?;
+
+    match get_hosted_bundle( &entity.content ) {
+        Ok(bundle) => Ok( bundle ),
+        Err(err) => {
+            debug!("DevHub hosts unavailable ({:?}); using mirror", err );
+            get_mirrored_bundle( &entity )
+        },
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    hdk,
+    get_moderator_action_history,
+};
+
+use hdk::prelude::*;
+use appstore::{
+    LinkTypes,
+    GroupAnchorEntry,
+    ModerationLogItem,
+    ModerationSubjectType,
+    ModerationLogExport,
+    ModerationLogVerification,
+
+    hc_crud::{
+        now,
+    },
+    hdi_extensions::{
+        AnyLinkableHashTransformer,
+    },
+};
+
+
+
+/// Get every moderator action (across all subjects) for the given Group ID, ordered by time
+pub(crate) fn get_moderation_log_handler(group_id: &ActionHash) -> ExternResult<Vec<ModerationLogItem>> {
+    let group_anchor_hash = hash_entry( &GroupAnchorEntry {
+        group_id: group_id.clone(),
+    })?;
+
+    let moderator_action_links = get_links(
+        GetLinksInputBuilder::try_new(
+            group_anchor_hash,
+            LinkTypes::GroupAnchorToModeratorAction,
+        )?.build()
+    )?;
+
+    let mut items = vec![];
+
+    for link in moderator_action_links {
+        let origin = link.target.must_be_action_hash()?;
+        // Skip links whose tag does not name a known subject type
+        let subject_type = match ModerationSubjectType::from_tag( &link.tag.0 ) {
+            Some(subject_type) => subject_type,
+            None => continue,
+        };
+
+        for entity in get_moderator_action_history( group_id, origin.clone() )? {
+            items.push( ModerationLogItem {
+                id: origin.clone(),
+                action: entity.action,
+                address: entity.address,
+                subject_id: entity.content.subject_id.clone(),
+                subject_type,
+                author: entity.content.author.clone(),
+                group_rev: entity.content.group_id.1.clone(),
+                published_at: entity.content.published_at,
+                message: entity.content.message.clone(),
+                action_type: entity.content.action_type(),
+                metadata: entity.content.metadata,
+            });
+        }
+    }
+
+    items.sort_by(|a, b| {
+        a.published_at.cmp( &b.published_at )
+            .then_with( || a.action.cmp( &b.action ) )
+    });
+
+    Ok( items )
+}
+
+
+/// Get the full, time-ordered moderation history for the given Group ID
+#[hdk_extern]
+pub fn get_moderation_log(group_id: ActionHash) -> ExternResult<Vec<ModerationLogItem>> {
+    debug!("Get moderation log for group: {}", group_id );
+    get_moderation_log_handler( &group_id )
+}
+
+
+/// Export the moderation history for the given Group ID as msgpack bytes
+#[hdk_extern]
+pub fn export_moderation_log(group_id: ActionHash) -> ExternResult<SerializedBytes> {
+    debug!("Export moderation log for group: {}", group_id );
+    let export = ModerationLogExport {
+        items: get_moderation_log_handler( &group_id )?,
+        exported_at: now()?,
+        group_id,
+    };
+
+    Ok( SerializedBytes::try_from( export )? )
+}
+
+
+/// Check a previously exported moderation log against the current DHT state
+#[hdk_extern]
+pub fn verify_moderation_log_export(bytes: SerializedBytes) -> ExternResult<ModerationLogVerification> {
+    let export = ModerationLogExport::try_from( bytes )?;
+    debug!("Verify moderation log export for group: {}", export.group_id );
+    let current = get_moderation_log_handler( &export.group_id )?;
+
+    // Items in the export must exist with the same entry address
+    let mismatched : Vec<ActionHash> = export.items.iter()
+        .filter(|item| {
+            !current.iter().any(|cur| cur.action == item.action && cur.address == item.address )
+        })
+        .map(|item| item.action.clone() )
+        .collect();
+
+    // Actions that existed at export time must be in the export
+    let missing : Vec<ActionHash> = current.iter()
+        .filter(|cur| cur.published_at <= export.exported_at )
+        .filter(|cur| !export.items.iter().any(|item| item.action == cur.action ) )
+        .map(|cur| cur.action.clone() )
+        .collect();
+
+    Ok( ModerationLogVerification {
+        verified: mismatched.is_empty() && missing.is_empty(),
+        mismatched,
+        missing,
+    })
+}
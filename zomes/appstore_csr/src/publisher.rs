@@ -1,5 +1,8 @@
 use crate::{
     hdk,
+    change_log::{
+        record_change,
+    },
 };
 
 use std::collections::BTreeMap;
@@ -8,9 +11,10 @@ use hdk_extensions::{
     agent_id,
 };
 use appstore::{
+    SchemaVersioned,
     LinkTypes,
-    RmpvValue,
-    WebAddress,
+    ChangeSubjectType,
+    ChangeType,
     DeprecationNotice,
 
     ALL_PUBLISHERS_ANCHOR,
@@ -19,28 +23,16 @@ use appstore::{
     hc_crud::{
         now, create_entity, get_entity, update_entity,
         Entity,
-        GetEntityInput, UpdateEntityInput,
+        GetEntityInput,
     },
 };
-
-
-
-#[derive(Debug, Deserialize)]
-pub struct CreateInput {
-    pub name: String,
-    pub location: String,
-    pub website: WebAddress,
-
-    // optional
-    pub description: Option<String>,
-    pub email: Option<String>,
-    pub icon: Option<EntryHash>,
-    pub editors: Option<Vec<AgentPubKey>>,
-
-    pub published_at: Option<u64>,
-    pub last_updated: Option<u64>,
-    pub metadata: Option<BTreeMap<String, RmpvValue>>,
-}
+pub use appstore_sdk::{
+    CreatePublisherInput as CreateInput,
+    UpdatePublisherProperties as UpdateProperties,
+    UpdatePublisherInput as UpdateInput,
+    DeprecatePublisherInput as DeprecateInput,
+    UndeprecatePublisherInput as UndeprecateInput,
+};
 
 
 #[hdk_extern]
@@ -77,6 +69,9 @@ pub fn create_publisher(mut input: CreateInput) -> ExternResult<Entity<Publisher
 	email: input.email,
 	icon: input.icon,
 	deprecation: None,
+	provenance: input.provenance,
+
+	schema_version: PublisherEntry::SCHEMA_VERSION,
     };
     let entity = create_entity( &publisher )?;
 
@@ -96,6 +91,9 @@ pub fn create_publisher(mut input: CreateInput) -> ExternResult<Entity<Publisher
             None
         )?;
     }
+    { // Path via Change Log
+	record_change( ChangeSubjectType::Publisher, ChangeType::Create, &entity.id, &entity.action )?;
+    }
 
     Ok( entity )
 }
@@ -110,21 +108,6 @@ pub fn get_publisher(input: GetEntityInput) -> ExternResult<Entity<PublisherEntr
 }
 
 
-#[derive(Debug, Deserialize, Clone)]
-pub struct UpdateProperties {
-    pub name: Option<String>,
-    pub description: Option<String>,
-    pub location: Option<String>,
-    pub website: Option<WebAddress>,
-    pub icon: Option<EntryHash>,
-    pub email: Option<String>,
-    pub editors: Option<Vec<AgentPubKey>>,
-    pub published_at: Option<u64>,
-    pub last_updated: Option<u64>,
-    pub metadata: Option<BTreeMap<String, RmpvValue>>,
-}
-pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
-
 #[hdk_extern]
 pub fn update_publisher(input: UpdateInput) -> ExternResult<Entity<PublisherEntry>> {
     debug!("Updating Publisher: {}", input.base );
@@ -153,19 +136,17 @@ pub fn update_publisher(input: UpdateInput) -> ExternResult<Entity<PublisherEntr
 	    current.metadata = props.metadata
 		.unwrap_or( current.metadata );
 
+	    current.upgrade();
+
 	    Ok( current )
 	})?;
 
+    record_change( ChangeSubjectType::Publisher, ChangeType::Update, &entity.id, &entity.action )?;
+
     Ok( entity )
 }
 
 
-#[derive(Debug, Deserialize)]
-pub struct DeprecateInput {
-    pub base: ActionHash,
-    pub message: String,
-}
-
 #[hdk_extern]
 pub fn deprecate_publisher(input: DeprecateInput) -> ExternResult<Entity<PublisherEntry>> {
     debug!("Deprecating publisher: {}", input.base );
@@ -174,21 +155,20 @@ pub fn deprecate_publisher(input: DeprecateInput) -> ExternResult<Entity<Publish
 	|mut current : PublisherEntry, _| {
 	    current.deprecation = Some(DeprecationNotice {
 		message: input.message.to_owned(),
-		recommended_alternatives: None,
+		recommended_alternatives: input.recommended_alternatives.to_owned(),
 	    });
 
+	    current.upgrade();
+
 	    Ok( current )
 	})?;
 
+    record_change( ChangeSubjectType::Publisher, ChangeType::Deprecate, &entity.id, &entity.action )?;
+
     Ok( entity )
 }
 
 
-#[derive(Debug, Deserialize)]
-pub struct UndeprecateInput {
-    pub base: ActionHash,
-}
-
 #[hdk_extern]
 pub fn undeprecate_publisher(input: UndeprecateInput) -> ExternResult<Entity<PublisherEntry>> {
     debug!("Undeprecating publisher: {}", input.base );
@@ -197,8 +177,12 @@ pub fn undeprecate_publisher(input: UndeprecateInput) -> ExternResult<Entity<Pub
 	|mut current : PublisherEntry, _| {
 	    current.deprecation = None;
 
+	    current.upgrade();
+
 	    Ok( current )
 	})?;
 
+    record_change( ChangeSubjectType::Publisher, ChangeType::Update, &entity.id, &entity.action )?;
+
     Ok( entity )
 }
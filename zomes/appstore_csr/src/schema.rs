@@ -0,0 +1,129 @@
+use crate::{
+    hdk,
+    get_my_publishers,
+    get_my_apps,
+    get_app_versions_for_app,
+    GetForAppInput,
+    change_log::{
+        record_change,
+    },
+};
+
+use hdk::prelude::*;
+use hdk_extensions::{
+    agent_id,
+};
+use appstore::{
+    SchemaVersioned,
+    ChangeSubjectType,
+    ChangeType,
+    PublisherEntry,
+    AppEntry,
+    AppVersionEntry,
+
+    hc_crud::{
+        update_entity,
+        Entity,
+    },
+};
+pub use appstore_sdk::{
+    SchemaUpgradeReport,
+};
+
+
+/// Re-write this agent's Publishers that were written with an older schema
+#[hdk_extern]
+pub fn upgrade_my_publishers(_: ()) -> ExternResult<Vec<Entity<PublisherEntry>>> {
+    let mut upgraded = vec![];
+
+    for entity in get_my_publishers(())? {
+	if !entity.content.needs_upgrade() {
+	    continue;
+	}
+
+	let updated = update_entity(
+	    &entity.action,
+	    |mut current : PublisherEntry, _| {
+		current.author = agent_id()?;
+		current.upgrade();
+
+		Ok( current )
+	    })?;
+
+	record_change( ChangeSubjectType::Publisher, ChangeType::Update, &updated.id, &updated.action )?;
+	upgraded.push( updated );
+    }
+
+    Ok( upgraded )
+}
+
+
+/// Re-write this agent's Apps that were written with an older schema
+#[hdk_extern]
+pub fn upgrade_my_apps(_: ()) -> ExternResult<Vec<Entity<AppEntry>>> {
+    let mut upgraded = vec![];
+
+    for entity in get_my_apps(())? {
+	if !entity.content.needs_upgrade() {
+	    continue;
+	}
+
+	let updated = update_entity(
+	    &entity.action,
+	    |mut current : AppEntry, _| {
+		current.author = agent_id()?;
+		current.upgrade();
+
+		Ok( current )
+	    })?;
+
+	record_change( ChangeSubjectType::App, ChangeType::Update, &updated.id, &updated.action )?;
+	upgraded.push( updated );
+    }
+
+    Ok( upgraded )
+}
+
+
+/// Re-write the versions of this agent's Apps that were written with an older schema
+#[hdk_extern]
+pub fn upgrade_my_app_versions(_: ()) -> ExternResult<Vec<Entity<AppVersionEntry>>> {
+    let mut upgraded = vec![];
+
+    for app in get_my_apps(())? {
+	let versions = get_app_versions_for_app( GetForAppInput {
+	    for_app: app.id,
+	})?;
+
+	for entity in versions {
+	    if !entity.content.needs_upgrade() {
+		continue;
+	    }
+
+	    let updated = update_entity(
+		&entity.action,
+		|mut current : AppVersionEntry, _| {
+		    current.author = agent_id()?;
+		    current.upgrade();
+
+		    Ok( current )
+		})?;
+
+	    record_change( ChangeSubjectType::AppVersion, ChangeType::Update, &updated.id, &updated.action )?;
+	    upgraded.push( updated );
+	}
+    }
+
+    Ok( upgraded )
+}
+
+
+/// Upgrade all of this agent's Publishers, Apps and App Versions to the current schema
+#[hdk_extern]
+pub fn upgrade_my_entries(_: ()) -> ExternResult<SchemaUpgradeReport> {
+    Ok( SchemaUpgradeReport {
+	publishers: upgrade_my_publishers(())?,
+	apps: upgrade_my_apps(())?,
+	app_versions: upgrade_my_app_versions(())?,
+    })
+}
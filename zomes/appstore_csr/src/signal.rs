@@ -0,0 +1,136 @@
+use crate::{
+    hdk,
+    hdi_extensions,
+};
+
+use hdk::prelude::*;
+use hdk_extensions::{
+    must_get,
+};
+use hdi_extensions::{
+    trace_origin_root,
+};
+use appstore::{
+    EntryTypes,
+    ChangeType,
+    AppStoreSignal,
+    PublisherEntry,
+    AppEntry,
+};
+
+
+
+/// Check if an update is the one that deprecated its entity
+fn is_deprecation<T>(action: &Action, deprecated: bool, was_deprecated: fn(T) -> bool) -> ExternResult<bool>
+where
+    T: TryFrom<Record, Error = WasmError>,
+{
+    match action {
+        Action::Update(update) if deprecated => {
+            let previous = T::try_from( must_get( &update.original_action_address )? )?;
+
+            Ok( !was_deprecated( previous ) )
+        },
+        _ => Ok( false ),
+    }
+}
+
+
+/// Build the [`AppStoreSignal`] for a committed action (`None` if it is not a store change)
+fn signal_for_action(signed_action: &SignedActionHashed) -> ExternResult<Option<AppStoreSignal>> {
+    let action = signed_action.action();
+    let action_hash = signed_action.as_hash().to_owned();
+
+    let (change_type, entity_action) = match action {
+        Action::Create(_) => ( ChangeType::Create, action_hash.clone() ),
+        Action::Update(_) => ( ChangeType::Update, action_hash.clone() ),
+        Action::Delete(delete) => ( ChangeType::Delete, delete.deletes_address.clone() ),
+        _ => return Ok( None ),
+    };
+
+    let record = must_get( &entity_action )?;
+    let entry_def = match record.action().entry_type() {
+        Some(EntryType::App(entry_def)) => entry_def.to_owned(),
+        _ => return Ok( None ),
+    };
+    let entry = match record.entry().as_option() {
+        Some(entry) => entry,
+        None => return Ok( None ),
+    };
+    let entry_type = match EntryTypes::deserialize_from_type(
+        entry_def.zome_index,
+        entry_def.entry_index,
+        entry,
+    )? {
+        Some(entry_type) => entry_type,
+        None => return Ok( None ),
+    };
+
+    let id = trace_origin_root( &entity_action )?.0;
+    let deleted = change_type == ChangeType::Delete;
+
+    Ok( match entry_type {
+        EntryTypes::Publisher(content) => {
+            let change_type = match is_deprecation(
+                action, content.deprecation.is_some(),
+                |previous : PublisherEntry| previous.deprecation.is_some(),
+            )? {
+                true => ChangeType::Deprecate,
+                false => change_type,
+            };
+
+            Some( AppStoreSignal::Publisher {
+                change_type,
+                id,
+                action: action_hash,
+                content: ( !deleted ).then_some( content ),
+            })
+        },
+        EntryTypes::App(content) => {
+            let change_type = match is_deprecation(
+                action, content.deprecation.is_some(),
+                |previous : AppEntry| previous.deprecation.is_some(),
+            )? {
+                true => ChangeType::Deprecate,
+                false => change_type,
+            };
+
+            Some( AppStoreSignal::App {
+                change_type,
+                id,
+                action: action_hash,
+                content: ( !deleted ).then_some( content ),
+            })
+        },
+        EntryTypes::AppVersion(content) => Some( AppStoreSignal::AppVersion {
+            change_type,
+            id,
+            action: action_hash,
+            content: ( !deleted ).then_some( content ),
+        }),
+        EntryTypes::ModeratorAction(content) => Some( AppStoreSignal::ModeratorAction {
+            change_type,
+            id,
+            action: action_hash,
+            content: ( !deleted ).then_some( content ),
+        }),
+        _ => None,
+    })
+}
+
+
+/// Emit an [`AppStoreSignal`] for each Publisher, App, App Version and Moderator Action change
+#[hdk_extern(infallible)]
+pub fn post_commit(committed_actions: Vec<SignedActionHashed>) {
+    for signed_action in committed_actions {
+        let result = signal_for_action( &signed_action )
+            .and_then( |signal| match signal {
+                Some(signal) => emit_signal( signal ),
+                None => Ok(()),
+            });
+
+        if let Err(err) = result {
+            error!("Failed to signal action ({}): {:?}", signed_action.as_hash(), err );
+        }
+    }
+}
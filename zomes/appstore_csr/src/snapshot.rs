@@ -0,0 +1,188 @@
+use crate::{
+    hdk,
+    get_all_publishers,
+    get_all_apps_handler,
+    get_app_versions_for_app,
+    GetForAppInput,
+    moderation_log::{
+        get_moderation_log_handler,
+    },
+    migration::{
+        export_icons,
+        import_entities,
+    },
+};
+
+use std::ops::Range;
+use hdk::prelude::*;
+use hdk_extensions::{
+    agent_id,
+};
+use appstore::{
+    AppStoreError,
+    CATALOG_SNAPSHOT_PAGE_SIZE,
+    CatalogSnapshot,
+    SignedCatalogSnapshot,
+    ModerationLogExport,
+    dna_properties,
+
+    hc_crud::{
+        now,
+    },
+};
+pub use appstore_sdk::{
+    ExportCatalogSnapshotInput,
+    MigrationImportReport,
+};
+
+
+
+/// Keep the entities whose position (counted from `offset`) falls inside `range`
+fn page_of<T>(entities: Vec<T>, offset: usize, range: &Range<usize>) -> Vec<T> {
+    entities.into_iter()
+        .enumerate()
+        .filter( |(index, _)| range.contains( &(offset + index) ) )
+        .map( |(_, entity)| entity )
+        .collect()
+}
+
+
+/// Export one page of every Publisher, App and App Version (including deprecated and removed
+/// ones) with icons and moderation logs, signed by this agent
+///
+/// Callers request pages starting at 0 until `more` is false.  Returns the msgpack encoded
+/// [`SignedCatalogSnapshot`].
+#[hdk_extern]
+pub fn export_catalog_snapshot(input: ExportCatalogSnapshotInput) -> ExternResult<SerializedBytes> {
+    let page = input.page.unwrap_or( 0 );
+    let page_size = input.page_size.unwrap_or( CATALOG_SNAPSHOT_PAGE_SIZE ).max( 1 ) as usize;
+    let start = page as usize * page_size;
+    let range = start..(start + page_size);
+
+    // Sorted so that every page request sees the same order
+    let mut publishers = get_all_publishers( None )?;
+    publishers.sort_by( |a, b| a.id.cmp( &b.id ) );
+    let mut apps = get_all_apps_handler( None )?;
+    apps.sort_by( |a, b| a.id.cmp( &b.id ) );
+    let mut app_versions = vec![];
+
+    for app in apps.iter() {
+        let mut versions = get_app_versions_for_app( GetForAppInput {
+            for_app: app.id.clone(),
+        })?;
+        versions.sort_by( |a, b| a.id.cmp( &b.id ) );
+        app_versions.extend( versions );
+    }
+
+    let total = publishers.len() + apps.len() + app_versions.len();
+    let apps_offset = publishers.len();
+    let versions_offset = apps_offset + apps.len();
+
+    let publishers = page_of( publishers, 0, &range );
+    let apps = page_of( apps, apps_offset, &range );
+    let app_versions = page_of( app_versions, versions_offset, &range );
+
+    let exported_at = now()?;
+    let mut moderation_logs = vec![];
+
+    if page == 0 {
+        let mut groups : Vec<ActionHash> = crate::governance::governance_group()?
+            .into_iter()
+            .chain( input.groups.unwrap_or_default() )
+            .collect();
+        groups.sort();
+        groups.dedup();
+
+        for group_id in groups {
+            moderation_logs.push( ModerationLogExport {
+                items: get_moderation_log_handler( &group_id )?,
+                exported_at,
+                group_id,
+            });
+        }
+    }
+
+    let snapshot = CatalogSnapshot {
+        dna: dna_info()?.hash,
+        exported_at,
+        exported_by: agent_id()?,
+        page,
+        more: range.end < total,
+        memories: export_icons( &publishers, &apps )?,
+        publishers,
+        apps,
+        app_versions,
+        moderation_logs,
+    };
+    debug!(
+        "Exporting catalog snapshot page {}: {} publishers, {} apps, {} versions",
+        page, snapshot.publishers.len(), snapshot.apps.len(), snapshot.app_versions.len(),
+    );
+
+    let bytes = SerializedBytes::try_from( snapshot )?;
+    let signature = sign_raw( agent_id()?, bytes.bytes().to_owned() )?;
+
+    Ok( SerializedBytes::try_from( SignedCatalogSnapshot {
+        snapshot: bytes,
+        signer: agent_id()?,
+        signature,
+    })? )
+}
+
+
+/// Verify a signed catalog snapshot page and re-create its entities in this (fresh) network
+///
+/// The signer must be one of the DNA's `snapshot_signers` (or this agent when unset) because a
+/// valid signature alone only proves that some agent signed the bytes.  Pages must be imported
+/// in order so that parents from earlier pages can be found.  Each copy gets a
+/// [`appstore::Provenance`] pointing at the original entity.  Moderation logs are not replayed
+/// because the groups do not exist in the new network.
+#[hdk_extern]
+pub fn import_catalog_snapshot(bytes: SerializedBytes) -> ExternResult<MigrationImportReport> {
+    let signed = SignedCatalogSnapshot::try_from( bytes )?;
+    let trusted = match dna_properties()?.snapshot_signers {
+        Some(signers) => signers,
+        None => vec![ agent_id()? ],
+    };
+
+    if !trusted.contains( &signed.signer ) {
+        return Err( AppStoreError::UntrustedSnapshotSigner {
+            signer: signed.signer,
+            trusted,
+        }.into() );
+    }
+
+    if !verify_signature_raw(
+        signed.signer.clone(),
+        signed.signature,
+        signed.snapshot.bytes().to_owned()
+    )? {
+        return Err( AppStoreError::InvalidSnapshotSignature {
+            signer: signed.signer,
+        }.into() );
+    }
+
+    let snapshot = CatalogSnapshot::try_from( signed.snapshot )?;
+
+    if snapshot.exported_by != signed.signer {
+        return Err( AppStoreError::SnapshotSignerMismatch {
+            exported_by: snapshot.exported_by,
+            signer: signed.signer,
+        }.into() );
+    }
+
+    if snapshot.dna == dna_info()?.hash {
+        return Err( AppStoreError::SnapshotFromThisDna {
+            dna: snapshot.dna,
+        }.into() );
+    }
+
+    debug!("Importing catalog snapshot page {} from DNA: {}", snapshot.page, snapshot.dna );
+    import_entities(
+        &snapshot.dna,
+        snapshot.publishers,
+        snapshot.apps,
+        snapshot.app_versions,
+        snapshot.memories,
+    )
+}
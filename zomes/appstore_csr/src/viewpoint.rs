@@ -0,0 +1,386 @@
+use crate::{
+    hdk,
+    get_moderated_state,
+    get_moderator_action_history,
+    get_subject_moderator_actions,
+    update_subject_moderated_state,
+    GetModeratorActionsInput,
+};
+
+use std::collections::HashSet;
+use hdk::prelude::*;
+use appstore::{
+    LinkTypes,
+    ALL_APPS_ANCHOR,
+    AppEntry,
+    PublisherEntry,
+    GroupAnchorEntry,
+    ModeratorActionEntry,
+    ModeratorActionType,
+    ModerationSubjectType,
+    removed_subject_tag,
+
+    hc_crud::{
+        get_entity,
+        Entity,
+        GetEntityInput,
+    },
+    hdi_extensions::{
+        AnyLinkableHashTransformer,
+    },
+};
+pub use appstore_sdk::{
+    ViewpointGetAppsInput,
+    ViewpointAppsPage,
+    ViewpointAppsCursor,
+    ViewpointGetInput,
+    ViewpointEntity,
+    GetPublisherModeratorActionsInput,
+    UpdatePublisherModeratorActionInput,
+};
+
+
+const DEFAULT_PAGE_LIMIT : usize = 100;
+
+/// Width of the first [`LinkTypes::AllAppsToApp`] time window read for a page (1 day)
+const APP_LINK_WINDOW_MICROS : i64 = 86_400_000_000;
+
+
+/// Keep the Group's removed subject index in line with the latest moderator action for a subject
+pub(crate) fn update_removed_subject_index(
+    subject_type: &str,
+    moderator_action: &Entity<ModeratorActionEntry>,
+) -> ExternResult<()> {
+    let group_id = moderator_action.content.group_id.0.clone();
+    let subject_id = moderator_action.content.subject_id.clone();
+    let group_anchor_hash = hash_entry( &GroupAnchorEntry {
+        group_id: group_id,
+    })?;
+
+    let index_links = get_links(
+        GetLinksInputBuilder::try_new(
+            group_anchor_hash.clone(),
+            LinkTypes::GroupAnchorToRemovedSubject,
+        )?
+            .tag_prefix( LinkTag::new( format!("{}::", subject_type ) ) )
+            .build()
+    )?;
+
+    // Clear any previous state for this subject
+    for link in index_links {
+        if link.target == subject_id.clone().into() {
+            delete_link( link.create_link_hash, GetOptions::default() )?;
+        }
+    }
+
+    if moderator_action.content.action_type() == ModeratorActionType::Remove {
+        create_link(
+            group_anchor_hash,
+            subject_id,
+            LinkTypes::GroupAnchorToRemovedSubject,
+            removed_subject_tag( subject_type, &moderator_action.action ),
+        )?;
+    }
+
+    Ok(())
+}
+
+
+/// Get the IDs of subjects (of the given type) that are currently removed in the given Group
+pub(crate) fn get_removed_subject_ids(
+    group_id: &ActionHash,
+    subject_type: &str,
+) -> ExternResult<HashSet<ActionHash>> {
+    let group_anchor_hash = hash_entry( &GroupAnchorEntry {
+        group_id: group_id.clone(),
+    })?;
+
+    let index_links = get_links(
+        GetLinksInputBuilder::try_new(
+            group_anchor_hash,
+            LinkTypes::GroupAnchorToRemovedSubject,
+        )?
+            .tag_prefix( LinkTag::new( format!("{}::", subject_type ) ) )
+            .build()
+    )?;
+
+    index_links.into_iter()
+        .map( |link| link.target.must_be_action_hash() )
+        .collect()
+}
+
+
+/// Get the IDs of apps removed by any of the given Groups (or by the governance group)
+pub(crate) fn get_viewpoint_removed_app_ids(
+    group_ids: &[ActionHash],
+) -> ExternResult<HashSet<ActionHash>> {
+    let mut removed_app_ids = crate::governance::get_governance_removed_app_ids()?;
+
+    for group_id in group_ids {
+        removed_app_ids.extend( get_removed_subject_ids( group_id, "app" )? );
+    }
+
+    debug!("Removed app IDs from viewpoint {:?}: {:#?}", group_ids, removed_app_ids );
+    Ok( removed_app_ids )
+}
+
+
+/// Get the IDs (with their [`LinkTypes::AllAppsToApp`] link time) of apps that are not removed,
+/// ordered by link time then ID and starting after the given cursor
+///
+/// Links are read in time windows that double in width, starting at the cursor, so a page only
+/// reads as many links as it needs.  Reading stops once more than `limit` IDs are found.
+fn get_viewpoint_app_ids(
+    removed_app_ids: &HashSet<ActionHash>,
+    after: Option<&ViewpointAppsCursor>,
+    limit: Option<usize>,
+) -> ExternResult<Vec<ViewpointAppsCursor>> {
+    let now = sys_time()?;
+    let first = match after {
+        Some(cursor) => cursor.timestamp,
+        None => dna_info()?.modifiers.origin_time,
+    };
+    // Windows overlap by 1µs so that links on a window edge are never skipped
+    let mut start = Timestamp::from_micros( first.as_micros() - 1 );
+    let mut width = APP_LINK_WINDOW_MICROS;
+    let mut seen = HashSet::new();
+    let mut app_ids = vec![];
+
+    while start <= now {
+        let end = Timestamp::from_micros( start.as_micros().saturating_add( width ) );
+        let links = get_links(
+            GetLinksInputBuilder::try_new(
+                ALL_APPS_ANCHOR.path_entry_hash()?,
+                LinkTypes::AllAppsToApp,
+            )?
+                .after( start )
+                .before( end )
+                .build()
+        )?;
+
+        let mut window = vec![];
+
+        for link in links {
+            let item = ViewpointAppsCursor {
+                app_id: link.target.must_be_action_hash()?,
+                timestamp: link.timestamp,
+            };
+
+            if let Some(cursor) = after {
+                if ( item.timestamp, &item.app_id ) <= ( cursor.timestamp, &cursor.app_id ) {
+                    continue;
+                }
+            }
+
+            if !removed_app_ids.contains( &item.app_id ) && seen.insert( item.app_id.clone() ) {
+                window.push( item );
+            }
+        }
+
+        window.sort_by(|a, b| {
+            a.timestamp.cmp( &b.timestamp )
+                .then_with( || a.app_id.cmp( &b.app_id ) )
+        });
+        app_ids.extend( window );
+
+        if let Some(limit) = limit {
+            if app_ids.len() > limit {
+                break;
+            }
+        }
+
+        start = Timestamp::from_micros( end.as_micros() - 1 );
+        width = width.saturating_mul( 2 );
+    }
+
+    Ok( app_ids )
+}
+
+
+/// Get the App entities for the given IDs, skipping (and logging) any that cannot be fetched
+fn get_app_entities<'a>(
+    app_ids: impl IntoIterator<Item = &'a ActionHash>,
+) -> Vec<Entity<AppEntry>> {
+    app_ids.into_iter()
+        .filter_map(|app_id| match get_entity( app_id ) {
+            Ok(entity) => Some( entity ),
+            Err(err) => {
+                debug!("Skipping app ({}) that could not be fetched: {:?}", app_id, err );
+                None
+            },
+        })
+        .collect()
+}
+
+
+/// Get all apps from the perspective of the given Groups
+pub(crate) fn get_viewpoint_apps(
+    group_ids: &[ActionHash],
+) -> ExternResult<Vec<Entity<AppEntry>>> {
+    let removed_app_ids = get_viewpoint_removed_app_ids( group_ids )?;
+    let app_ids = get_viewpoint_app_ids( &removed_app_ids, None, None )?;
+
+    Ok( get_app_entities( app_ids.iter().map( |item| &item.app_id ) ) )
+}
+
+
+/// Get all removed apps (sorted by ID) from the perspective of the given Groups
+pub(crate) fn get_viewpoint_removed_apps(
+    group_ids: &[ActionHash],
+) -> ExternResult<Vec<Entity<AppEntry>>> {
+    let mut app_ids : Vec<ActionHash> = get_viewpoint_removed_app_ids( group_ids )?
+        .into_iter()
+        .collect();
+    app_ids.sort();
+
+    Ok( get_app_entities( app_ids.iter() ) )
+}
+
+
+/// Get a page of apps from the perspective of the given Group ID
+///
+/// Pass the returned `next` cursor as `after` to get the following page.
+#[hdk_extern]
+pub fn viewpoint_get_apps(input: ViewpointGetAppsInput) -> ExternResult<ViewpointAppsPage> {
+    let limit = input.limit.unwrap_or( DEFAULT_PAGE_LIMIT );
+    let removed_app_ids = get_viewpoint_removed_app_ids( &[ input.group_id ] )?;
+    let mut app_ids = get_viewpoint_app_ids( &removed_app_ids, input.after.as_ref(), Some( limit ) )?;
+
+    let more = app_ids.len() > limit;
+    app_ids.truncate( limit );
+
+    let total = count_links(
+        LinkQuery::try_new(
+            ALL_APPS_ANCHOR.path_entry_hash()?,
+            LinkTypes::AllAppsToApp,
+        )?
+    )?.saturating_sub( removed_app_ids.len() );
+
+    Ok( ViewpointAppsPage {
+        items: get_app_entities( app_ids.iter().map( |item| &item.app_id ) ),
+        next: match more {
+            true => app_ids.last().cloned(),
+            false => None,
+        },
+        total,
+    })
+}
+
+
+/// Rebuild the removed subject index of the given Group from its moderator actions
+///
+/// Moderator actions made before the index existed are not in it; any group contributor can call
+/// this once to add them.  Returns the number of subjects whose latest state was indexed.
+#[hdk_extern]
+pub fn backfill_removed_subject_index(group_id: ActionHash) -> ExternResult<usize> {
+    debug!("Backfilling removed subject index for group: {}", group_id );
+    let group_anchor_hash = hash_entry( &GroupAnchorEntry {
+        group_id: group_id.clone(),
+    })?;
+
+    let moderator_action_links = get_links(
+        GetLinksInputBuilder::try_new(
+            group_anchor_hash,
+            LinkTypes::GroupAnchorToModeratorAction,
+        )?.build()
+    )?;
+
+    let mut count = 0;
+
+    for link in moderator_action_links {
+        let subject_type = match ModerationSubjectType::from_tag( &link.tag.0 ) {
+            Some(subject_type) => subject_type,
+            None => continue,
+        };
+        let origin = link.target.must_be_action_hash()?;
+
+        if let Some(latest) = get_moderator_action_history( &group_id, origin )?.last() {
+            update_removed_subject_index( subject_type.tag_prefix(), latest )?;
+            count += 1;
+        }
+    }
+
+    Ok( count )
+}
+
+
+fn is_removed(state: &Option<Entity<ModeratorActionEntry>>) -> bool {
+    match state {
+        Some(entity) => entity.content.action_type() == ModeratorActionType::Remove,
+        None => false,
+    }
+}
+
+
+/// Get an App along with its moderated state from the perspective of the given Group ID
+#[hdk_extern]
+pub fn viewpoint_get_app(input: ViewpointGetInput) -> ExternResult<ViewpointEntity<AppEntry>> {
+    debug!("Get app {} from viewpoint {}", input.id, input.group_id );
+    let entity = crate::app::get_app( GetEntityInput {
+        id: input.id.clone(),
+    })?;
+    let moderated_state = get_moderated_state( GetModeratorActionsInput {
+        group_id: input.group_id,
+        app_id: entity.id.clone(),
+    })?;
+
+    Ok( ViewpointEntity {
+        removed: is_removed( &moderated_state ),
+        moderated_state,
+        entity,
+    })
+}
+
+
+/// Get a Publisher along with its moderated state from the perspective of the given Group ID
+#[hdk_extern]
+pub fn viewpoint_get_publisher(input: ViewpointGetInput) -> ExternResult<ViewpointEntity<PublisherEntry>> {
+    debug!("Get publisher {} from viewpoint {}", input.id, input.group_id );
+    let entity = crate::publisher::get_publisher( GetEntityInput {
+        id: input.id.clone(),
+    })?;
+    let moderated_state = get_publisher_moderated_state( GetPublisherModeratorActionsInput {
+        group_id: input.group_id,
+        publisher_id: entity.id.clone(),
+    })?;
+
+    Ok( ViewpointEntity {
+        removed: is_removed( &moderated_state ),
+        moderated_state,
+        entity,
+    })
+}
+
+
+/// Get moderator actions for the given Publisher ID that were created by the members of the given
+/// Group ID
+#[hdk_extern]
+pub fn get_publisher_moderator_actions(input: GetPublisherModeratorActionsInput) -> ExternResult<Vec<Entity<ModeratorActionEntry>>> {
+    get_subject_moderator_actions(
+        &input.group_id,
+        format!("publisher::{}", input.publisher_id ),
+    )
+}
+
+/// Get the latest moderated state for a given Group ID and Publisher ID
+#[hdk_extern]
+pub fn get_publisher_moderated_state(input: GetPublisherModeratorActionsInput) -> ExternResult<Option<Entity<ModeratorActionEntry>>> {
+    let history = get_publisher_moderator_actions(input)?;
+    let state = history.last()
+        .map( |state| state.to_owned() );
+
+    Ok( state )
+}
+
+
+/// Update the moderated state for the given Publisher ID from the viewpoint of the given Group ID
+#[hdk_extern]
+pub fn update_publisher_moderated_state(input: UpdatePublisherModeratorActionInput) -> ExternResult<Entity<ModeratorActionEntry>> {
+    update_subject_moderated_state(
+        input.group_id,
+        "publisher",
+        input.publisher_id,
+        input.message,
+        input.metadata,
+    )
+}
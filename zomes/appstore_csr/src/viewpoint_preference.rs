@@ -0,0 +1,115 @@
+use crate::{
+    hdk,
+    get_all_apps,
+    viewpoint::{
+        get_viewpoint_apps,
+    },
+};
+
+use hdk::prelude::*;
+use hdk_extensions::{
+    agent_id,
+};
+use appstore::{
+    EntryTypesUnit,
+    AppEntry,
+    ViewpointPreferenceEntry,
+
+    hc_crud::{
+        now,
+        Entity,
+        EntryModel,
+    },
+    hdi_extensions::{
+        ScopedTypeConnector,
+    },
+};
+pub use appstore_sdk::{
+    SetMyViewpointInput,
+};
+
+
+/// Get the current viewpoint preference from this agent's source chain
+///
+/// The preference is a private entry so other agents cannot see which groups this agent follows.
+/// Devices see the same preference when they run the same source chain (eg. a shared cell).
+pub(crate) fn get_my_viewpoint_handler() -> ExternResult<Option<Entity<ViewpointPreferenceEntry>>> {
+    let records = query(
+        ChainQueryFilter::new()
+            .entry_type( EntryTypesUnit::ViewpointPreference.try_into()? )
+            .include_entries( true )
+    )?;
+
+    let (origin, latest) = match ( records.first(), records.last() ) {
+        ( Some(first), Some(last) ) => ( first.action_address().to_owned(), last ),
+        _ => return Ok( None ),
+    };
+    let preference = ViewpointPreferenceEntry::try_from( latest.to_owned() )?;
+
+    Ok( Some( Entity {
+        id: origin,
+        address: hash_entry( preference.clone() )?,
+        action: latest.action_address().to_owned(),
+        ctype: preference.get_type(),
+        content: preference,
+    }))
+}
+
+
+/// Get the viewpoint preference of the current cell agent
+#[hdk_extern]
+pub fn get_my_viewpoint(_: ()) -> ExternResult<Option<Entity<ViewpointPreferenceEntry>>> {
+    get_my_viewpoint_handler()
+}
+
+
+/// Save the viewpoint preference (a list of Group IDs) of the current cell agent
+#[hdk_extern]
+pub fn set_my_viewpoint(input: SetMyViewpointInput) -> ExternResult<Entity<ViewpointPreferenceEntry>> {
+    debug!("Setting viewpoint preference: {:?}", input.group_ids );
+    let current = get_my_viewpoint_handler()?;
+    let default_now = now()?;
+
+    let preference = ViewpointPreferenceEntry {
+	group_ids: input.group_ids,
+	author: agent_id()?,
+	published_at: current.as_ref()
+	    .map( |entity| entity.content.published_at )
+	    .unwrap_or( default_now ),
+	last_updated: default_now,
+    };
+
+    let (id, action) = match current {
+        Some(entity) => (
+            entity.id,
+            update_entry( entity.action, preference.clone().to_input() )?,
+        ),
+        None => {
+            let action = create_entry( preference.clone().to_input() )?;
+            ( action.clone(), action )
+        },
+    };
+
+    Ok( Entity {
+        id: id,
+        address: hash_entry( preference.clone() )?,
+        action: action,
+        ctype: preference.get_type(),
+        content: preference,
+    })
+}
+
+
+/// Get all apps using the saved viewpoint preference of the current cell agent
+///
+/// An app is excluded if any of the preferred groups removed it.  When no preference has been
+/// saved, this is the same as `get_all_apps`.
+#[hdk_extern]
+pub fn my_view_get_all_apps(_: ()) -> ExternResult<Vec<Entity<AppEntry>>> {
+    let group_ids = match get_my_viewpoint_handler()? {
+        Some(entity) if !entity.content.group_ids.is_empty() => entity.content.group_ids,
+        _ => return get_all_apps(None),
+    };
+
+    get_viewpoint_apps( &group_ids )
+}